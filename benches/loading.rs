@@ -2,7 +2,7 @@
 extern crate test;
 #[cfg(test)]
 mod tests {
-    use spel_right::load_words_dict;
+    use spel_right::{DICT_VERSION, DictMetadata, SpellChecker, load_binary_dict, load_words_dict};
     use test::Bencher;
 
     static WORDS_FILE: &str = "words.txt";
@@ -11,4 +11,23 @@ mod tests {
     fn words_loading_from_file(b: &mut Bencher) {
         b.iter(|| load_words_dict(WORDS_FILE));
     }
+
+    #[bench]
+    fn words_loading_from_binary(b: &mut Bencher) {
+        let file = std::env::temp_dir().join(format!(
+            "spel_right_loading_bench_{}.bin",
+            std::process::id()
+        ));
+        let meta = DictMetadata {
+            language_full: "English".into(),
+            language_short: "en_us".into(),
+            version: DICT_VERSION,
+            words_amount: 0,
+            included_encodings: vec!["ascii".into()],
+        };
+        SpellChecker::new(WORDS_FILE)
+            .save_binary(&file, &meta)
+            .unwrap();
+        b.iter(|| load_binary_dict(&file));
+    }
 }
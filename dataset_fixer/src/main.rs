@@ -1,42 +1,81 @@
-use std::fs;
+use std::{collections::BTreeMap, env, fs, process};
 
+/// Converts a list of words, one per line, into the dictionary format of `load_words_dict`.
+///
+/// Lines can also be `word<TAB>count`, counts are then written as word frequencies.
+/// Counts of words appearing more than once (in any case) are added up.
+///
+/// Usage: `dataset_fixer <input> <output>`, exits with `1` on a line with a malformed count.
 fn main() {
-    let file1 = "C:/dev/tools/basic-spellchecker/words_alpha.txt";
-    let file2 = "C:/dev/tools/basic-spellchecker/words.txt";
-    let dataset = fs::read_to_string(file1).unwrap();
-    let mut dataset = dataset
-        .lines()
-        .filter_map(|l| {
-            let word = l.trim().to_lowercase();
-            if word.len() == 0usize {
-                None
-            } else {
-                Some(word.to_string())
+    let mut args = env::args().skip(1);
+    let (Some(file1), Some(file2)) = (args.next(), args.next()) else {
+        fail("usage: dataset_fixer <input> <output>");
+    };
+    let dataset = fs::read_to_string(&file1).unwrap_or_else(|error| fail(&format!("{file1}: {error}")));
+
+    let mut has_counts = false;
+    let mut counts: BTreeMap<String, u32> = BTreeMap::new();
+    for (i, line) in dataset.lines().enumerate() {
+        let (word, count) = match line.split_once('\t') {
+            Some((word, count)) => {
+                has_counts = true;
+                let count = count.trim().parse::<u32>().unwrap_or_else(|error| {
+                    fail(&format!("{file1}:{}: bad count {count:?}: {error}", i + 1))
+                });
+                (word, count)
             }
-        })
-        .collect::<Vec<String>>();
-    
-    dataset.sort_by(|w1, w2| w1.len().cmp(&w2.len()).then(w1.cmp(w2)));
+            None => (line, 0),
+        };
+        let word = word.trim().to_lowercase();
+        if word.is_empty() {
+            continue;
+        }
+        let entry = counts.entry(word).or_insert(0);
+        *entry = entry.saturating_add(count);
+    }
+
+    let mut dataset: Vec<(String, u32)> = counts.into_iter().collect();
+    dataset.sort_by(|(w1, _), (w2, _)| w1.len().cmp(&w2.len()).then(w1.cmp(w2)));
 
     let mut formatted_output = String::new();
+    let mut frequencies = String::new();
     let mut previous_len = 0;
 
-    for word in dataset {
+    for (word, count) in dataset {
         let current_len = word.len();
         
         // If the length changes AND it's not the very first word
         if current_len != previous_len {
             if previous_len != 0 {
-                formatted_output.push_str("\n");
+                push_frequencies(&mut formatted_output, &mut frequencies, has_counts);
+                formatted_output.push('\n');
             }
             formatted_output.push_str(&current_len.to_string());
-            formatted_output.push_str("\n");
+            formatted_output.push('\n');
         }
         
         formatted_output.push_str(&word);
+        frequencies.push(' ');
+        frequencies.push_str(&count.to_string());
         
         previous_len = current_len;
     }
+    push_frequencies(&mut formatted_output, &mut frequencies, has_counts);
 
-    fs::write(file2, formatted_output).unwrap();
-}
\ No newline at end of file
+    fs::write(&file2, formatted_output).unwrap_or_else(|error| fail(&format!("{file2}: {error}")));
+}
+
+/// Prints the `message` and exits with `1`.
+fn fail(message: &str) -> ! {
+    eprintln!("{message}");
+    process::exit(1);
+}
+
+/// Writes the `#` line with the frequencies of the last group, if the dataset had counts.
+fn push_frequencies(output: &mut String, frequencies: &mut String, has_counts: bool) {
+    if has_counts && !frequencies.is_empty() {
+        output.push_str("\n#");
+        output.push_str(frequencies);
+    }
+    frequencies.clear();
+}
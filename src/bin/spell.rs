@@ -1,17 +1,33 @@
-use std::env::{self, current_exe};
+use std::{
+    env::{self, current_exe},
+    fs,
+    path::{Path, PathBuf},
+    process,
+};
 
-use spel_right::SpellChecker;
+use spel_right::{English, Language, Markup, SourceLanguage, SpellChecker, TextChecker};
 
 fn main() {
     let path = current_exe().unwrap();
     let path = path.parent().unwrap();
-    let path = path.join("words.txt");
 
-    let mut checker = SpellChecker::new(path);
+    // The binary dictionary is memory mapped, so it starts faster than the text one
+    let binary = path.join("words.bin");
+    let mut checker = if binary.exists() {
+        SpellChecker::new_binary(binary).unwrap()
+    } else {
+        SpellChecker::new(path.join("words.txt"))
+    };
     checker.max_dif = 4;
 
     let mut return_elements = 10;
     let args: Vec<String> = env::args().collect();
+    // `spell code src/` checks comments and string literals of source files, `spell docs docs/` prose of markup files
+    match args.get(1).map(String::as_str) {
+        Some("code") => return check_files(&checker, &args[2..], is_source_file),
+        Some("docs") => return check_files(&checker, &args[2..], is_markup_file),
+        _ => {}
+    }
     let words_to_check = args.get(1..).unwrap();
     let words_to_check: Vec<&str> = words_to_check
         .iter()
@@ -30,10 +46,103 @@ fn main() {
     for (word, suggestion) in suggestions {
         if suggestion.is_empty() {
             println!("❌ Wrong word '{word}', no suggestions")
-        } else if suggestion.len() == 1 {
+        } else if suggestion[0].distance == 0 {
             println!("✅ {word}")
         } else {
-            println!("❓ {word} => {}", &suggestion.join(" "))
+            let suggestion: Vec<&str> = suggestion.iter().map(|s| &*s.word).collect();
+            println!("❓ {word} => {}", suggestion.join(" "))
+        }
+    }
+}
+
+/// Checks the files at the given `paths` that are `checked`, walking directories.
+///
+/// Exits with `2` if a path is missing, has no checked files or can't be read, otherwise with `1` if any word is misspelled.
+fn check_files(checker: &SpellChecker, paths: &[String], checked: fn(&Path) -> bool) {
+    let checker = TextChecker::new(checker, English::tokenizer());
+
+    let mut failed = false;
+    let mut files = vec![];
+    for path in paths {
+        let path = Path::new(path);
+        let found = files.len();
+        if !collect_files(path, checked, &mut files) {
+            failed = true;
+        } else if files.len() == found {
+            eprintln!("{}: no files to check", path.display());
+            failed = true;
+        }
+    }
+    let mut misspelled = false;
+    for file in files {
+        match checker.check_file(&file, 5) {
+            Ok(diagnostics) => {
+                for diagnostic in diagnostics {
+                    misspelled = true;
+                    println!("{diagnostic}");
+                }
+            }
+            Err(error) => {
+                eprintln!("{}: {error}", file.display());
+                failed = true;
+            }
         }
     }
+    if failed {
+        process::exit(2);
+    }
+    if misspelled {
+        process::exit(1);
+    }
+}
+
+/// Collects the `path` if it's `checked`, or the checked files under it if it's a directory.
+///
+/// Paths that are missing or can't be read are printed, returns `false` if there were any.
+fn collect_files(path: &Path, checked: fn(&Path) -> bool, files: &mut Vec<PathBuf>) -> bool {
+    let metadata = match fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(error) => {
+            eprintln!("{}: {error}", path.display());
+            return false;
+        }
+    };
+    if !metadata.is_dir() {
+        if checked(path) {
+            files.push(path.to_owned());
+        }
+        return true;
+    }
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            eprintln!("{}: {error}", path.display());
+            return false;
+        }
+    };
+    let mut ok = true;
+    let mut paths = vec![];
+    for entry in entries {
+        match entry {
+            Ok(entry) => paths.push(entry.path()),
+            Err(error) => {
+                eprintln!("{}: {error}", path.display());
+                ok = false;
+            }
+        }
+    }
+    paths.sort();
+    for path in paths {
+        ok &= collect_files(&path, checked, files);
+    }
+    ok
+}
+
+fn is_source_file(path: &Path) -> bool {
+    SourceLanguage::from_path(path).is_some()
+}
+
+fn is_markup_file(path: &Path) -> bool {
+    Markup::from_path(path).is_some()
 }
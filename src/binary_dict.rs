@@ -0,0 +1,352 @@
+use std::{
+    fmt,
+    fs::File,
+    io::{self, BufWriter, Write},
+    ops::{Deref, Range},
+    path::Path,
+    sync::{Arc, OnceLock},
+};
+
+use memmap2::Mmap;
+use serde::{Deserialize, Serialize};
+
+use crate::{DictMetadata, LenGroup, WordBlob, spell_checkers::simple_len_group::WordGroup};
+
+const MAGIC: &[u8; 4] = b"SRWD";
+const VERSION: u32 = 1;
+
+/// Words of a group, either owned or borrowed from a memory mapped binary dictionary.
+///
+/// Derefs to `str`, a mapped blob is only copied when it's changed with `to_mut()`.
+///
+/// A mapped blob is checked to be valid utf-8 the first time it's used, and panics if it isn't.
+#[derive(Clone, Default)]
+pub struct Blob(BlobRepr);
+
+#[derive(Clone)]
+enum BlobRepr {
+    Owned(String),
+    /// Range of the map, with whether it's valid utf-8 once it was first used.
+    Mapped(Arc<Mmap>, Range<usize>, OnceLock<bool>),
+}
+
+impl Default for BlobRepr {
+    fn default() -> Self {
+        Self::Owned(String::new())
+    }
+}
+
+impl Blob {
+    /// Gets the blob as a mutable `String`, copying it out of the map first if needed.
+    pub fn to_mut(&mut self) -> &mut String {
+        if let BlobRepr::Mapped(..) = self.0 {
+            self.0 = BlobRepr::Owned(String::from(&**self));
+        }
+        match &mut self.0 {
+            BlobRepr::Owned(blob) => blob,
+            BlobRepr::Mapped(..) => unreachable!(),
+        }
+    }
+
+    /// Checks if the blob is borrowed from a memory mapped file.
+    pub fn is_mapped(&self) -> bool {
+        matches!(self.0, BlobRepr::Mapped(..))
+    }
+
+    fn mapped(map: &Arc<Mmap>, range: Range<usize>) -> Self {
+        Self(BlobRepr::Mapped(map.clone(), range, OnceLock::new()))
+    }
+}
+
+impl Deref for Blob {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match &self.0 {
+            BlobRepr::Owned(blob) => blob,
+            BlobRepr::Mapped(map, range, valid) => {
+                let bytes = &map[range.clone()];
+                // Checked the first time the group is used, so pages of unused groups are never read
+                if !*valid.get_or_init(|| std::str::from_utf8(bytes).is_ok()) {
+                    panic!("blob of a binary dictionary is not valid utf-8");
+                }
+                // Safety: the bytes were just checked to be valid utf-8, and the map is read only
+                unsafe { std::str::from_utf8_unchecked(bytes) }
+            }
+        }
+    }
+}
+
+impl From<String> for Blob {
+    fn from(blob: String) -> Self {
+        Self(BlobRepr::Owned(blob))
+    }
+}
+
+impl fmt::Debug for Blob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(self.deref(), f)
+    }
+}
+
+impl PartialEq for Blob {
+    fn eq(&self, other: &Self) -> bool {
+        self.deref() == other.deref()
+    }
+}
+
+impl Serialize for Blob {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Blob {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// Frequency of each word of a group, either owned or borrowed from a memory mapped binary dictionary.
+#[derive(Clone, Default)]
+pub struct Frequencies(FreqRepr);
+
+#[derive(Clone)]
+enum FreqRepr {
+    Owned(Vec<u32>),
+    /// Little endian `u32`s.
+    Mapped(Arc<Mmap>, Range<usize>),
+}
+
+impl Default for FreqRepr {
+    fn default() -> Self {
+        Self::Owned(vec![])
+    }
+}
+
+impl Frequencies {
+    /// Gets the frequency of the word at `index`.
+    pub fn get(&self, index: usize) -> Option<u32> {
+        match &self.0 {
+            FreqRepr::Owned(freq) => freq.get(index).copied(),
+            FreqRepr::Mapped(map, range) => {
+                let start = range.start.checked_add(index.checked_mul(4)?)?;
+                if start + 4 > range.end {
+                    return None;
+                }
+                Some(u32::from_le_bytes(
+                    map[start..start + 4].try_into().unwrap(),
+                ))
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match &self.0 {
+            FreqRepr::Owned(freq) => freq.len(),
+            FreqRepr::Mapped(_, range) => range.len() / 4,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = u32> + '_ {
+        (0..self.len()).filter_map(|i| self.get(i))
+    }
+
+    /// Gets the frequencies as a mutable `Vec`, copying them out of the map first if needed.
+    pub fn to_mut(&mut self) -> &mut Vec<u32> {
+        if let FreqRepr::Mapped(..) = self.0 {
+            self.0 = FreqRepr::Owned(self.iter().collect());
+        }
+        match &mut self.0 {
+            FreqRepr::Owned(freq) => freq,
+            FreqRepr::Mapped(..) => unreachable!(),
+        }
+    }
+}
+
+impl From<Vec<u32>> for Frequencies {
+    fn from(freq: Vec<u32>) -> Self {
+        Self(FreqRepr::Owned(freq))
+    }
+}
+
+impl fmt::Debug for Frequencies {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+impl PartialEq<Vec<u32>> for Frequencies {
+    fn eq(&self, other: &Vec<u32>) -> bool {
+        self.len() == other.len() && self.iter().eq(other.iter().copied())
+    }
+}
+
+impl Serialize for Frequencies {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for Frequencies {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Vec::<u32>::deserialize(deserializer).map(Self::from)
+    }
+}
+
+/// A group of words with the same length that can be saved into a binary dictionary.
+pub(crate) trait DictGroup: WordBlob + Sized {
+    fn frequencies(&self) -> &Frequencies;
+    fn from_parts(len: usize, count: usize, blob: Blob, freq: Frequencies) -> Self;
+}
+
+impl DictGroup for LenGroup {
+    fn frequencies(&self) -> &Frequencies {
+        &self.freq
+    }
+
+    fn from_parts(len: usize, count: usize, blob: Blob, freq: Frequencies) -> Self {
+        Self {
+            blob,
+            len,
+            count,
+            freq,
+        }
+    }
+}
+
+impl DictGroup for WordGroup {
+    fn frequencies(&self) -> &Frequencies {
+        &self.freq
+    }
+
+    fn from_parts(len: usize, count: usize, blob: Blob, freq: Frequencies) -> Self {
+        Self {
+            blob,
+            len,
+            count,
+            freq,
+        }
+    }
+}
+
+/// Saves the `groups` with their `meta` into a binary dictionary `file`, see [`load_binary_dict()`].
+pub fn save_binary_dict(
+    file: impl AsRef<Path>,
+    meta: &DictMetadata,
+    groups: &[LenGroup],
+) -> io::Result<()> {
+    save_groups(file, meta, groups)
+}
+
+/// Loads a binary dictionary saved with [`save_binary_dict()`], returning its metadata and groups.
+///
+/// The file is laid out as:
+///
+/// 1. The `SRWD` magic and the `u32` version of the format.
+/// 2. The length of the `DictMetadata` and the `DictMetadata` itself, as json.
+/// 3. The amount of groups and a table with the `length`, `count` and amount of frequencies of each group, all `u32`.
+/// 4. The raw blob of each group, followed by its frequencies as `u32`s, if it has any.
+///
+/// All numbers are little endian. The file is memory mapped and groups borrow their blobs and frequencies from the map,
+/// so nothing is copied or parsed, and pages are only read once they're used.
+/// Blobs are checked to be valid utf-8 the first time their group is used, a corrupted blob panics then.
+///
+/// The file must not be changed while it's loaded.
+pub fn load_binary_dict(file: impl AsRef<Path>) -> io::Result<(DictMetadata, Vec<LenGroup>)> {
+    load_groups(file)
+}
+
+pub(crate) fn save_groups<G: DictGroup>(
+    file: impl AsRef<Path>,
+    meta: &DictMetadata,
+    groups: &[G],
+) -> io::Result<()> {
+    let meta = serde_json::to_vec(meta)?;
+    let mut writer = BufWriter::new(File::create(file)?);
+    writer.write_all(MAGIC)?;
+    for value in [VERSION, meta.len() as u32] {
+        writer.write_all(&value.to_le_bytes())?;
+    }
+    writer.write_all(&meta)?;
+
+    writer.write_all(&(groups.len() as u32).to_le_bytes())?;
+    for group in groups {
+        for value in [
+            group.word_len(),
+            group.words_count(),
+            group.frequencies().len(),
+        ] {
+            writer.write_all(&(value as u32).to_le_bytes())?;
+        }
+    }
+    for group in groups {
+        writer.write_all(group.blob())?;
+        for freq in group.frequencies().iter() {
+            writer.write_all(&freq.to_le_bytes())?;
+        }
+    }
+    writer.flush()
+}
+
+pub(crate) fn load_groups<G: DictGroup>(
+    file: impl AsRef<Path>,
+) -> io::Result<(DictMetadata, Vec<G>)> {
+    let file = File::open(file)?;
+    // Safety: the map is read only, and the file is documented to not be changed while loaded
+    let map = Arc::new(unsafe { Mmap::map(&file)? });
+    let mut pos = 0;
+    let mut take = |len: usize| -> io::Result<Range<usize>> {
+        let end = pos + len;
+        if end > map.len() {
+            return Err(invalid("binary dictionary is cut short"));
+        }
+        let range = pos..end;
+        pos = end;
+        Ok(range)
+    };
+    let read_u32 = |range: Range<usize>| u32::from_le_bytes(map[range].try_into().unwrap());
+
+    if &map[take(4)?] != MAGIC {
+        return Err(invalid("not a binary dictionary file"));
+    }
+    let version = read_u32(take(4)?);
+    if version != VERSION {
+        return Err(invalid(format!(
+            "unsupported binary dictionary version {version}"
+        )));
+    }
+    let meta_len = read_u32(take(4)?) as usize;
+    let meta: DictMetadata = serde_json::from_slice(&map[take(meta_len)?])?;
+
+    let groups_len = read_u32(take(4)?) as usize;
+    let mut table = Vec::with_capacity(groups_len);
+    for _ in 0..groups_len {
+        let len = read_u32(take(4)?) as usize;
+        let count = read_u32(take(4)?) as usize;
+        let freq_count = read_u32(take(4)?) as usize;
+        // Groups are indexed by length, starting from `1`
+        if len != table.len() + 1 || (freq_count != 0 && freq_count != count) {
+            return Err(invalid(format!(
+                "invalid group of length {len} with {count} words"
+            )));
+        }
+        table.push((len, count, freq_count));
+    }
+
+    let mut groups = Vec::with_capacity(groups_len);
+    for (len, count, freq_count) in table {
+        let blob = Blob::mapped(&map, take(len * count)?);
+        let freq = Frequencies(FreqRepr::Mapped(map.clone(), take(freq_count * 4)?));
+        groups.push(G::from_parts(len, count, blob, freq));
+    }
+    Ok((meta, groups))
+}
+
+fn invalid(error: impl Into<Box<dyn std::error::Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, error)
+}
@@ -0,0 +1,131 @@
+use std::ops::Range;
+
+/// How a language builds compound words out of dictionary words, like German `Donaudampfschifffahrt`.
+///
+/// Companion of [`crate::Language`], languages that build compounds return their rules from `compounds()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompoundRules {
+    /// Minimum amount of chars of each part, so short words don't split everything.
+    pub min_part_len: usize,
+    /// Morphemes that can join two parts, like the German `s` of `Arbeitszimmer`.
+    pub linking_morphemes: &'static [&'static str],
+}
+
+impl CompoundRules {
+    /// Splits the lowercase `word` into two or more parts `is_word` accepts, linking morphemes left out.
+    ///
+    /// Longer parts are preferred, `None` if the `word` can't be split.
+    pub fn split<'a>(&self, word: &'a str, is_word: impl Fn(&str) -> bool) -> Option<Vec<&'a str>> {
+        let bounds = char_bounds(word);
+        let from = self.parts_from(word, &bounds, &is_word);
+        let mut parts = vec![];
+        let mut start = 0;
+        while start < bounds.len() - 1 {
+            let (end, next) = from[start]?;
+            parts.push(&word[bounds[start]..bounds[end]]);
+            start = next;
+        }
+        (parts.len() > 1).then_some(parts)
+    }
+
+    /// Byte ranges of the lowercase `word` that would make it a compound if they were a word, everything around them being made of parts `is_word` accepts.
+    ///
+    /// Used to suggest corrections to a single part of a compound.
+    pub fn unknown_parts(&self, word: &str, is_word: impl Fn(&str) -> bool) -> Vec<Range<usize>> {
+        let bounds = char_bounds(word);
+        let len = bounds.len() - 1;
+        let from = self.parts_from(word, &bounds, &is_word);
+
+        // Whether a part can start at each char, everything before it being parts and linking morphemes
+        let mut starts = vec![false; len + 1];
+        starts[0] = true;
+        for start in 0..len {
+            if !starts[start] {
+                continue;
+            }
+            for end in start + self.part_len()..=len {
+                if is_word(&word[bounds[start]..bounds[end]]) {
+                    for next in self.links(word, &bounds, end) {
+                        starts[next] = true;
+                    }
+                }
+            }
+        }
+
+        let mut ranges = vec![];
+        for start in (0..len).filter(|start| starts[*start]) {
+            for end in start + self.part_len()..=len {
+                let rest_is_parts = end == len
+                    || self
+                        .links(word, &bounds, end)
+                        .any(|next| next < len && from[next].is_some());
+                if rest_is_parts && (start, end) != (0, len) {
+                    ranges.push(bounds[start]..bounds[end]);
+                }
+            }
+        }
+        ranges
+    }
+
+    /// For each char, the end of the first part and the start of the next one, if the `word` can be split from there.
+    ///
+    /// The whole `word` never counts as a single part.
+    fn parts_from(
+        &self,
+        word: &str,
+        bounds: &[usize],
+        is_word: &impl Fn(&str) -> bool,
+    ) -> Vec<Option<(usize, usize)>> {
+        let len = bounds.len() - 1;
+        let mut from = vec![None; len + 1];
+        for start in (0..len).rev() {
+            for end in (start + self.part_len()..=len).rev() {
+                if (start, end) == (0, len) {
+                    continue;
+                }
+                let next = if end == len {
+                    Some(len)
+                } else {
+                    self.links(word, bounds, end)
+                        .find(|next| *next < len && from[*next].is_some())
+                };
+                if let Some(next) = next
+                    && is_word(&word[bounds[start]..bounds[end]])
+                {
+                    from[start] = Some((end, next));
+                    break;
+                }
+            }
+        }
+        from
+    }
+
+    /// Minimum amount of chars of a part, at least `1`.
+    fn part_len(&self) -> usize {
+        self.min_part_len.max(1)
+    }
+
+    /// Chars where the next part can start after a part ending at char `end`, with or without a linking morpheme.
+    fn links<'a>(
+        &'a self,
+        word: &'a str,
+        bounds: &'a [usize],
+        end: usize,
+    ) -> impl Iterator<Item = usize> + 'a {
+        let rest = &word[bounds[end]..];
+        std::iter::once(end).chain(
+            self.linking_morphemes
+                .iter()
+                .filter(move |morpheme| !morpheme.is_empty() && rest.starts_with(**morpheme))
+                .map(move |morpheme| end + morpheme.chars().count()),
+        )
+    }
+}
+
+/// Byte offset of each char of the `word`, followed by its length.
+fn char_bounds(word: &str) -> Vec<usize> {
+    word.char_indices()
+        .map(|(i, _)| i)
+        .chain([word.len()])
+        .collect()
+}
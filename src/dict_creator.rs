@@ -1,6 +1,6 @@
-use std::marker::PhantomData;
+use std::{collections::HashMap, marker::PhantomData};
 
-use crate::{DICT_VERSION, DictMetadata, Language, SC, spell_checkers::{ascii, normalized, utf8, words_to_groups}};
+use crate::{DICT_VERSION, DictMetadata, Hunspell, Language, SC, spell_checker::{default_max_dif, default_scorer}, spell_checkers::{affix, ascii, normalized::{self, normalize, words_to_norm_groups}, trie, utf8::{self, words_to_char_groups}, words_to_groups}};
 
 #[derive(Debug, Default)]
 pub struct SpellCheckerBuilder<L: Language> {
@@ -8,6 +8,11 @@ pub struct SpellCheckerBuilder<L: Language> {
     ascii: Option<ascii::SpellChecker>,
     norm: Option<normalized::SpellChecker>,
     utf8: Option<utf8::SpellChecker>,
+    trie: Option<trie::SpellChecker>,
+    affix: Option<affix::SpellChecker>,
+    bk_tree: bool,
+    frequencies: Option<HashMap<String, u32>>,
+    forbidden: HashMap<String, Option<String>>,
     lang: PhantomData<L>,
 }
 
@@ -23,45 +28,126 @@ impl<L: Language + Default> SpellCheckerBuilder<L> {
         Some(self)
     }
 
-    /// WIP
-    /// 
-    /// Normalized words will be checked against their ascii counterparts
-    /// and mapped to utf-8 forms
-    pub fn add_norm_words(&mut self, _words: Vec<String>) -> Option<&mut Self> {
-        // self.word_amount += words.len();
-        // let ascii_groups = words_to_groups(
-        //     words
-        //         .par_iter()
-        //         .map(|w: &str| {
-        //             w.nfd()
-        //                 .filter(|ch| ch.is_ascii())
-        //                 .collect::<String>()
-        //         })
-        //         .collect()
-        // )?;
-        // for word in words {
-
-        // }
-        // self.norm = Some(words);
+    /// Adds words with diacritics, like `café` or `naïve`.
+    ///
+    /// Words are checked against their ascii counterparts and mapped to utf-8 forms,
+    /// words with chars that have no ascii form are skipped and not counted.
+    pub fn add_norm_words(&mut self, words: Vec<String>) -> Option<&mut Self> {
+        let groups = words_to_norm_groups(words)?;
+        self.word_amount += groups.iter().map(|group| group.count()).sum::<usize>();
+        self.norm = Some(normalized::SpellChecker::new(groups));
         Some(self)
     }
 
     pub fn add_utf8_words(&mut self, words: Vec<String>) -> Option<&mut Self> {
         self.word_amount += words.len();
-        let groups = words_to_groups(words)?;
+        let groups = words_to_char_groups(words)?;
         self.utf8 = Some(utf8::SpellChecker::new(groups));
         Some(self)
     }
 
+    /// Adds every word of a Hunspell dictionary, expanded with its affix rules.
+    ///
+    /// Words are split by encoding, ascii words go to the ascii checker, words with diacritics to the normalized one and the rest to the utf-8 one.
+    /// Returns `None` without adding anything if one of those checkers was already added, as its words would be replaced.
+    pub fn add_hunspell_words(&mut self, dict: &Hunspell) -> Option<&mut Self> {
+        let (ascii, other): (Vec<String>, Vec<String>) = dict.expand().into_iter().partition(|w| w.is_ascii());
+        let (norm, utf8): (Vec<String>, Vec<String>) = other.into_iter().partition(|w| normalize(w).is_some());
+        if ascii.is_empty() && norm.is_empty() && utf8.is_empty() { return None }
+        if (!ascii.is_empty() && self.ascii.is_some()) || (!norm.is_empty() && self.norm.is_some()) || (!utf8.is_empty() && self.utf8.is_some()) { return None }
+        if !ascii.is_empty() { self.add_ascii_words(ascii)?; }
+        if !norm.is_empty() { self.add_norm_words(norm)?; }
+        if !utf8.is_empty() { self.add_utf8_words(utf8)?; }
+        Some(self)
+    }
+
+    /// Adds the stems and affix rules of a Hunspell dictionary, without expanding them.
+    ///
+    /// Inflected words are checked by stripping their affixes, see [`affix::SpellChecker`].
+    /// Takes 10 to 100 times less space than `add_hunspell_words()` for highly inflected languages, at the cost of slower checks.
+    pub fn add_hunspell_stems(&mut self, dict: &Hunspell) -> Option<&mut Self> {
+        self.word_amount += dict.stems.len();
+        self.affix = Some(affix::SpellChecker::new(dict)?);
+        Some(self)
+    }
+
+    /// Adds the words to a trie backed checker.
+    ///
+    /// Accepts the same words as `add_ascii_words`, suggestions are found by walking the trie with a Levenshtein automaton.
+    pub fn add_trie_words(&mut self, words: Vec<String>) -> Option<&mut Self> {
+        self.word_amount += words.len();
+        let groups = words_to_groups(words)?;
+        self.trie = Some(trie::SpellChecker::new(groups));
+        Some(self)
+    }
+
+    /// Builds a `BkTree` for each checker, so it's saved with the dictionary and not rebuilt on every start.
+    pub fn with_bk_tree(&mut self) -> &mut Self {
+        self.bk_tree = true;
+        self
+    }
+
+    /// Sets the frequency of words, saved with the dictionary and used to rank suggestions.
+    ///
+    /// Words are looked up lowercased, frequencies of words differing only in case are added up.
+    /// Words missing from `frequencies` get `0`.
+    pub fn with_frequencies(&mut self, frequencies: HashMap<String, u32>) -> &mut Self {
+        let mut lowercased = HashMap::with_capacity(frequencies.len());
+        for (word, freq) in frequencies {
+            let entry = lowercased.entry(word.to_lowercase()).or_insert(0u32);
+            *entry = entry.saturating_add(freq);
+        }
+        self.frequencies = Some(lowercased);
+        self
+    }
+
+    /// Forbids words, like slurs or words a style guide bans, so they're never correct nor suggested even if they're in the dictionary.
+    ///
+    /// Each word can have a preferred replacement, suggested first instead of it. Words are looked up lowercased.
+    pub fn with_forbidden_words(&mut self, words: HashMap<String, Option<String>>) -> &mut Self {
+        self.forbidden
+            .extend(words.into_iter().map(|(word, replacement)| (word.to_lowercase(), replacement)));
+        self
+    }
+
     pub fn build(&mut self) -> SC<L> {
+        if let Some(frequencies) = &self.frequencies {
+            let groups = [
+                self.ascii.as_mut().map(|c| c.groups_mut()),
+                self.trie.as_mut().map(|c| c.groups_mut()),
+            ];
+            for group in groups.into_iter().flatten().flatten() {
+                group.set_frequencies(frequencies);
+            }
+            for group in self.utf8.iter_mut().flat_map(|c| c.groups_mut()) {
+                group.set_frequencies(frequencies);
+            }
+            for group in self.norm.iter_mut().flat_map(|c| c.groups_mut()) {
+                group.set_frequencies(frequencies);
+            }
+            for group in self.affix.iter_mut().flat_map(|c| c.groups_mut()) {
+                group.set_frequencies(frequencies);
+            }
+        }
+        if self.bk_tree {
+            if let Some(checker) = &mut self.ascii { checker.build_bk_tree(); }
+            if let Some(checker) = &mut self.norm { checker.build_bk_tree(); }
+            if let Some(checker) = &mut self.utf8 { checker.build_bk_tree(); }
+            if let Some(checker) = &mut self.affix { checker.build_bk_tree(); }
+        }
+
+        // Trie words are ascii too, affix stems aren't split by encoding so they add none
         let mut encodings = vec![];
-        if self.ascii.is_some() { encodings.push("ascii".to_owned()) }
+        if self.ascii.is_some() || self.trie.is_some() { encodings.push("ascii".to_owned()) }
         if self.norm.is_some() { encodings.push("normalized".to_owned()) }
         if self.utf8.is_some() { encodings.push("utf-8".to_owned()) }
         SC {
             ascii_checker: self.ascii.take(),
             norm_checker: self.norm.take(),
             utf8_checker: self.utf8.take(),
+            trie_checker: self.trie.take(),
+            affix_checker: self.affix.take(),
+            forbidden: std::mem::take(&mut self.forbidden),
             dict_meta: DictMetadata {
                 language_full: L::full_name().into(),
                 language_short: L::name().into(),
@@ -70,6 +156,9 @@ impl<L: Language + Default> SpellCheckerBuilder<L> {
                 included_encodings: encodings,
             },
             language: PhantomData,
+            max_dif: default_max_dif(),
+            scorer: default_scorer::<L>(),
+            compounds: L::compounds(),
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+
+use crate::{Language, SC, Scorer, SpellChecker, Suggestion};
+
+/// A spell checker that can be a layer of a [`DictionaryStack`].
+pub trait Dictionary: Send + Sync {
+    fn check(&self, word: &str) -> bool;
+
+    /// Suggests words for the `word`, only the `word` itself if it's in the dictionary.
+    ///
+    /// `take_first_x` of `0` returns all of them.
+    fn suggest(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'_>>;
+
+    /// Finds all words close to the `word`, even if it's in the dictionary.
+    fn suggest_for_word(&self, word: &str) -> Vec<Suggestion<'_>>;
+}
+
+impl<L: Language> Dictionary for SC<L> {
+    fn check(&self, word: &str) -> bool {
+        self.check(word)
+    }
+
+    fn suggest(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'_>> {
+        self.suggest(word, take_first_x)
+    }
+
+    fn suggest_for_word(&self, word: &str) -> Vec<Suggestion<'_>> {
+        self.suggest_for_word(word)
+    }
+}
+
+impl Dictionary for SpellChecker {
+    fn check(&self, word: &str) -> bool {
+        self.check(word)
+    }
+
+    fn suggest(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'_>> {
+        self.suggest(word, take_first_x)
+    }
+
+    fn suggest_for_word(&self, word: &str) -> Vec<Suggestion<'_>> {
+        self.suggest_for_word(word.to_lowercase().as_bytes())
+    }
+}
+
+/// Several dictionaries checked as one, like the language dictionary, a project word list and a personal one.
+///
+/// A word is correct if any layer has it and no forbidden layer does.
+/// Layers are asked in the order they were added, earlier layers win when merging suggestions.
+#[derive(Default)]
+pub struct DictionaryStack {
+    layers: Vec<Box<dyn Dictionary>>,
+    forbidden: Vec<Box<dyn Dictionary>>,
+    /// Sets the `Scorer` used to order suggestions merged from all layers, [`crate::FrequencyScorer`] by default.
+    pub scorer: Box<dyn Scorer>,
+}
+
+impl DictionaryStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a layer of words that are correct.
+    pub fn add_layer(&mut self, dict: impl Dictionary + 'static) -> &mut Self {
+        self.layers.push(Box::new(dict));
+        self
+    }
+
+    /// Adds a layer of forbidden words, which are never correct nor suggested, even if other layers have them.
+    pub fn add_forbidden_layer(&mut self, dict: impl Dictionary + 'static) -> &mut Self {
+        self.forbidden.push(Box::new(dict));
+        self
+    }
+
+    /// Amount of layers, forbidden ones included.
+    pub fn len(&self) -> usize {
+        self.layers.len() + self.forbidden.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Checks if any layer has the `word`, and no forbidden layer has it.
+    pub fn check(&self, word: &str) -> bool {
+        !self.is_forbidden(word) && self.layers.iter().any(|dict| dict.check(word))
+    }
+
+    /// Checks if a forbidden layer has the `word`.
+    pub fn is_forbidden(&self, word: &str) -> bool {
+        self.forbidden.iter().any(|dict| dict.check(word))
+    }
+
+    pub fn batch_check<'a>(&self, words: &'a [&str]) -> Vec<(&'a str, bool)> {
+        words.iter().map(|&word| (word, self.check(word))).collect()
+    }
+
+    /// Suggests words for the `word` from all layers.
+    ///
+    /// If the `word` is correct, returns a vector with the given `word` from the first layer that has it.
+    /// Otherwise suggestions of all layers are merged, forbidden words and duplicates are left out,
+    /// and they're sorted by the `scorer`, taking the first `take_first_x`, `0` taking all of them.
+    pub fn suggest(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'_>> {
+        if self.check(word)
+            && let Some(exact) = self.layers.iter().find_map(|dict| {
+                dict.suggest(word, 1)
+                    .into_iter()
+                    .find(|suggestion| suggestion.distance == 0)
+            })
+        {
+            return vec![exact];
+        }
+
+        let lowercase = word.to_lowercase();
+        let mut seen = HashSet::new();
+        let mut scored: Vec<(f64, Suggestion)> = self
+            .layers
+            .iter()
+            .flat_map(|dict| {
+                // `suggest` of a layer that has the word, forbidden by another one, would only return the word, so its neighbours are asked for instead
+                if dict.check(word) {
+                    dict.suggest_for_word(word)
+                } else {
+                    dict.suggest(word, 0)
+                }
+            })
+            .filter(|suggestion| !self.is_forbidden(&suggestion.word))
+            .filter(|suggestion| seen.insert(suggestion.word.clone()))
+            .map(|suggestion| {
+                let score = self.scorer.score(lowercase.as_bytes(), &suggestion);
+                (score, suggestion)
+            })
+            .collect();
+        scored.sort_by(|(score1, _), (score2, _)| score1.total_cmp(score2));
+
+        let mut result: Vec<Suggestion> = scored
+            .into_iter()
+            .map(|(_, suggestion)| suggestion)
+            .collect();
+        if take_first_x != 0 {
+            result.truncate(take_first_x);
+        }
+        result
+    }
+
+    /// Suggests words for each `word` in the given `words`, see `suggest()`.
+    pub fn batch_suggest<'a>(
+        &self,
+        words: &'a [&str],
+        take_first_x: usize,
+    ) -> Vec<(&'a str, Vec<Suggestion<'_>>)> {
+        words
+            .iter()
+            .map(|&word| (word, self.suggest(word, take_first_x)))
+            .collect()
+    }
+}
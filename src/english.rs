@@ -1,8 +1,12 @@
-use crate::Language;
+use crate::{Language, TokenizerRules, keyboard::Qwerty, phonetic::Metaphone};
 
+#[derive(Debug, Default, Clone, Copy)]
 pub struct English;
 
 impl Language for English {
+    type Keyboard = Qwerty;
+    type Phonetic = Metaphone;
+
     fn name() -> &'static str {
         "en_us"
     }
@@ -10,4 +14,11 @@ impl Language for English {
     fn full_name() -> &'static str {
         "English (US)"
     }
-}
\ No newline at end of file
+
+    fn tokenizer() -> TokenizerRules {
+        TokenizerRules {
+            clitics: &["'s", "'re", "'ve", "'ll", "'d", "'m", "n't", "'t"],
+            ..TokenizerRules::default()
+        }
+    }
+}
@@ -0,0 +1,230 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use crate::{
+    Edit, KeyboardLayout, Language, Scorer, Suggestion,
+    keyboard::{Azerty, Dvorak, Qwerty, Qwertz},
+};
+
+/// Noisy channel `Scorer`, ranking suggestions by `P(candidate) * P(word | candidate)`.
+///
+/// Both probabilities are kept as costs (negative logs), so the score is the cost of the `edits` of a suggestion,
+/// minus `frequency_weight * ln(frequency + 1)` of the suggested word. Lower scores come first.
+///
+/// Each kind of `Edit` has a default cost, and specific bytes can have their own, e.g. substituting `e` with `a` can be cheap.
+/// Substitutions of keys next to each other on a [`KeyboardLayout`] can be made cheaper all at once.
+/// Edits are the ones of the `Suggestion`, the cheapest script by amount of edits, not by their costs.
+///
+/// The default model costs `1` per edit and ignores frequency beyond breaking ties, ranking the same as [`crate::FrequencyScorer`].
+#[derive(Debug, Clone)]
+pub struct ErrorModel {
+    /// Cost of a byte missing from the word, if it has no specific cost.
+    pub insert: f64,
+    /// Cost of an extra byte in the word, if it has no specific cost.
+    pub delete: f64,
+    /// Cost of a wrong byte in the word, if it has no specific cost.
+    pub substitute: f64,
+    /// Cost of two swapped bytes in the word, if they have no specific cost.
+    pub transpose: f64,
+    /// Weight of the frequency of the suggested word.
+    pub frequency_weight: f64,
+    /// Missing byte -> cost.
+    insertions: HashMap<u8, f64>,
+    /// Extra byte -> cost.
+    deletions: HashMap<u8, f64>,
+    /// `(typed, intended)` -> cost.
+    substitutions: HashMap<(u8, u8), f64>,
+    /// `(typed first, intended first)` -> cost.
+    transpositions: HashMap<(u8, u8), f64>,
+}
+
+impl Default for ErrorModel {
+    fn default() -> Self {
+        Self {
+            insert: 1.,
+            delete: 1.,
+            substitute: 1.,
+            transpose: 1.,
+            frequency_weight: 0.,
+            insertions: HashMap::new(),
+            deletions: HashMap::new(),
+            substitutions: HashMap::new(),
+            transpositions: HashMap::new(),
+        }
+    }
+}
+
+impl ErrorModel {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Model for the language `L`, with substitutions of keys next to each other on its keyboard costing `0.75`.
+    ///
+    /// Default scorer of [`crate::SC`]. Suggestions with fewer edits still come first,
+    /// but among as many edits, typos of adjacent keys rank first, like `fox` for `foz`.
+    pub fn for_language<L: Language>() -> Self {
+        let mut model = Self::default();
+        model.set_keyboard::<L::Keyboard>(0.75);
+        model
+    }
+
+    /// Sets the cost of `missing` being left out of the word.
+    pub fn set_insertion(&mut self, missing: u8, cost: f64) -> &mut Self {
+        self.insertions.insert(missing, cost);
+        self
+    }
+
+    /// Sets the cost of an `extra` byte in the word.
+    pub fn set_deletion(&mut self, extra: u8, cost: f64) -> &mut Self {
+        self.deletions.insert(extra, cost);
+        self
+    }
+
+    /// Sets the cost of `typed` being in the word instead of `intended`.
+    ///
+    /// Costs are one way, set both ways for symmetric confusions.
+    pub fn set_substitution(&mut self, typed: u8, intended: u8, cost: f64) -> &mut Self {
+        self.substitutions.insert((typed, intended), cost);
+        self
+    }
+
+    /// Sets the cost of `first` being typed before `second`, when `second` should have been first.
+    pub fn set_transposition(&mut self, first: u8, second: u8, cost: f64) -> &mut Self {
+        self.transpositions.insert((first, second), cost);
+        self
+    }
+
+    /// Sets the cost of substituting keys next to each other on the keyboard `K`, both ways.
+    ///
+    /// Only ascii keys are used, since costs are per byte.
+    pub fn set_keyboard<K: KeyboardLayout>(&mut self, cost: f64) -> &mut Self {
+        for (a, b) in K::adjacent_pairs() {
+            if a.is_ascii() && b.is_ascii() {
+                self.set_substitution(a as u8, b as u8, cost);
+            }
+        }
+        self
+    }
+
+    /// Cost of a single `edit` that turns the `word` into the `candidate`.
+    pub fn edit_cost(&self, word: &[u8], candidate: &[u8], edit: Edit) -> f64 {
+        match edit {
+            Edit::Insert { candidate_pos, .. } => *self
+                .insertions
+                .get(&candidate[candidate_pos])
+                .unwrap_or(&self.insert),
+            Edit::Delete { pos } => *self.deletions.get(&word[pos]).unwrap_or(&self.delete),
+            Edit::Substitute { pos, candidate_pos } => *self
+                .substitutions
+                .get(&(word[pos], candidate[candidate_pos]))
+                .unwrap_or(&self.substitute),
+            Edit::Transpose { pos, candidate_pos } => *self
+                .transpositions
+                .get(&(word[pos], candidate[candidate_pos]))
+                .unwrap_or(&self.transpose),
+        }
+    }
+
+    /// Loads a model from a text `file`, see [`ErrorModel::parse`].
+    pub fn load(file: impl AsRef<Path>) -> io::Result<Self> {
+        Self::parse(&fs::read_to_string(file)?)
+    }
+
+    /// Parses a model, one cost per line.
+    ///
+    /// ```text
+    /// # Comments and empty lines are ignored
+    /// insert 1.2          default cost of a missing byte
+    /// delete 0.8          default cost of an extra byte
+    /// substitute 1        default cost of a wrong byte
+    /// transpose 0.7       default cost of swapped bytes
+    /// frequency 0.5       frequency_weight
+    /// insert e 0.5        `e` missing
+    /// delete s 0.6        extra `s`
+    /// substitute e a 0.3  `e` typed instead of `a`
+    /// transpose i e 0.4   `ie` typed instead of `ei`
+    /// keyboard qwerty 0.5 adjacent keys, see `set_keyboard`
+    /// ```
+    ///
+    /// Supported keyboards are `qwerty`, `azerty`, `qwertz` and `dvorak`. Later lines override earlier ones.
+    ///
+    /// Everything after the cost is ignored. Bytes are single ascii characters, other than digits.
+    pub fn parse(content: &str) -> io::Result<Self> {
+        let mut model = Self::default();
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |msg: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("error model line {}: {msg}", i + 1),
+                )
+            };
+
+            let mut parts = line.split_whitespace();
+            let op = parts.next().unwrap_or_default();
+            let args: Vec<&str> = parts.collect();
+            let byte = |arg: &str| match arg.as_bytes() {
+                [byte] => Ok(*byte),
+                _ => Err(invalid(&format!(
+                    "expected a single ascii character, got `{arg}`"
+                ))),
+            };
+            let cost = |at: usize| {
+                let arg = args.get(at).ok_or_else(|| invalid("missing cost"))?;
+                arg.parse::<f64>()
+                    .map_err(|_| invalid(&format!("invalid cost `{arg}`")))
+            };
+            // Default costs have a number right after the operation
+            let is_default = args.first().is_some_and(|arg| arg.parse::<f64>().is_ok());
+
+            match (op, is_default) {
+                ("insert", true) => model.insert = cost(0)?,
+                ("delete", true) => model.delete = cost(0)?,
+                ("substitute", true) => model.substitute = cost(0)?,
+                ("transpose", true) => model.transpose = cost(0)?,
+                ("frequency", true) => model.frequency_weight = cost(0)?,
+                ("keyboard", false) if !args.is_empty() => {
+                    let cost = cost(1)?;
+                    match args[0] {
+                        name if name == Qwerty::name() => model.set_keyboard::<Qwerty>(cost),
+                        name if name == Azerty::name() => model.set_keyboard::<Azerty>(cost),
+                        name if name == Qwertz::name() => model.set_keyboard::<Qwertz>(cost),
+                        name if name == Dvorak::name() => model.set_keyboard::<Dvorak>(cost),
+                        other => return Err(invalid(&format!("unknown keyboard `{other}`"))),
+                    };
+                }
+                ("insert", false) if !args.is_empty() => {
+                    model.set_insertion(byte(args[0])?, cost(1)?);
+                }
+                ("delete", false) if !args.is_empty() => {
+                    model.set_deletion(byte(args[0])?, cost(1)?);
+                }
+                ("substitute", false) if args.len() > 1 => {
+                    model.set_substitution(byte(args[0])?, byte(args[1])?, cost(2)?);
+                }
+                ("transpose", false) if args.len() > 1 => {
+                    model.set_transposition(byte(args[0])?, byte(args[1])?, cost(2)?);
+                }
+                _ => return Err(invalid(&format!("unknown entry `{line}`"))),
+            }
+        }
+        Ok(model)
+    }
+}
+
+impl Scorer for ErrorModel {
+    fn score(&self, word: &[u8], suggestion: &Suggestion) -> f64 {
+        let candidate = suggestion.word.as_bytes();
+        let channel: f64 = suggestion
+            .edits
+            .iter()
+            .map(|edit| self.edit_cost(word, candidate, *edit))
+            .sum();
+        let prior = self.frequency_weight * (suggestion.frequency as f64).ln_1p();
+        // Same tie breaker as the `FrequencyScorer`
+        channel - prior - suggestion.frequency as f64 / (u32::MAX as f64 + 1.)
+    }
+}
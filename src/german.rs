@@ -0,0 +1,24 @@
+use crate::{CompoundRules, Language, keyboard::Qwertz, phonetic::Soundex};
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct German;
+
+impl Language for German {
+    type Keyboard = Qwertz;
+    type Phonetic = Soundex;
+
+    fn name() -> &'static str {
+        "de_de"
+    }
+
+    fn full_name() -> &'static str {
+        "German (Germany)"
+    }
+
+    fn compounds() -> Option<CompoundRules> {
+        Some(CompoundRules {
+            min_part_len: 3,
+            linking_morphemes: &["s", "es", "n", "en", "er", "e"],
+        })
+    }
+}
@@ -0,0 +1,510 @@
+use std::{collections::HashMap, fs, io, path::Path};
+
+use serde::{Deserialize, Serialize};
+
+/// Flag of a Hunspell affix class or word property.
+///
+/// Single char flags are stored as their code point, long flags as two code points and numeric flags as their number.
+pub type Flag = u32;
+
+/// How flags are written in the `.aff` and `.dic` files, set with the `FLAG` directive.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlagType {
+    /// One char per flag, the default.
+    #[default]
+    Short,
+    /// Two chars per flag.
+    Long,
+    /// Numbers separated by commas.
+    Num,
+}
+
+impl FlagType {
+    fn parse(self, flags: &str) -> Option<Vec<Flag>> {
+        match self {
+            Self::Short => Some(flags.chars().map(|ch| ch as Flag).collect()),
+            Self::Long => {
+                let chars: Vec<char> = flags.chars().collect();
+                if !chars.len().is_multiple_of(2) {
+                    return None;
+                }
+                Some(
+                    chars
+                        .chunks(2)
+                        .map(|pair| ((pair[0] as Flag) << 16) | (pair[1] as Flag & 0xFFFF))
+                        .collect(),
+                )
+            }
+            Self::Num => flags
+                .split(',')
+                .filter(|flag| !flag.is_empty())
+                .map(|flag| flag.parse().ok())
+                .collect(),
+        }
+    }
+}
+
+/// One unit of an affix condition.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+enum ConditionChar {
+    Any,
+    Char(char),
+    /// `[abc]`, or `[^abc]` if negated.
+    Set(Vec<char>, bool),
+}
+
+impl ConditionChar {
+    fn matches(&self, ch: char) -> bool {
+        match self {
+            Self::Any => true,
+            Self::Char(expected) => *expected == ch,
+            Self::Set(chars, negated) => chars.contains(&ch) != *negated,
+        }
+    }
+}
+
+/// Condition of an affix rule, a simplified regex like `[^aeiou]y` matched at the start of a word for prefixes, or at its end for suffixes.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Condition(Vec<ConditionChar>);
+
+impl Condition {
+    fn parse(condition: &str) -> Option<Self> {
+        let mut units = vec![];
+        let mut chars = condition.chars();
+        while let Some(ch) = chars.next() {
+            units.push(match ch {
+                '.' => ConditionChar::Any,
+                '[' => {
+                    let mut set = vec![];
+                    let mut negated = false;
+                    loop {
+                        match chars.next()? {
+                            ']' => break,
+                            '^' if set.is_empty() && !negated => negated = true,
+                            ch => set.push(ch),
+                        }
+                    }
+                    ConditionChar::Set(set, negated)
+                }
+                ch => ConditionChar::Char(ch),
+            });
+        }
+        // A lone `.` matches any word
+        if units == [ConditionChar::Any] {
+            units.clear();
+        }
+        Some(Self(units))
+    }
+
+    pub(crate) fn matches_start(&self, word: &str) -> bool {
+        let mut chars = word.chars();
+        self.0
+            .iter()
+            .all(|unit| chars.next().is_some_and(|ch| unit.matches(ch)))
+    }
+
+    fn matches_end(&self, word: &str) -> bool {
+        let mut chars = word.chars().rev();
+        self.0
+            .iter()
+            .rev()
+            .all(|unit| chars.next().is_some_and(|ch| unit.matches(ch)))
+    }
+}
+
+/// Whether an affix rule is added at the start or at the end of a word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AffixKind {
+    Prefix,
+    Suffix,
+}
+
+/// A single `PFX` or `SFX` entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AffixRule {
+    pub kind: AffixKind,
+    /// Removed from the stem before adding the affix, empty for none.
+    pub strip: String,
+    /// Added to the stem, empty for none.
+    pub add: String,
+    /// Flags of affixes that can be added after this one.
+    pub continuation: Vec<Flag>,
+    /// The stem has to match it for the rule to apply.
+    pub condition: Condition,
+}
+
+impl AffixRule {
+    /// Adds the affix to the `stem`, `None` if the rule doesn't apply to it.
+    pub fn apply(&self, stem: &str) -> Option<String> {
+        match self.kind {
+            AffixKind::Prefix => {
+                if !self.condition.matches_start(stem) {
+                    return None;
+                }
+                let rest = stem.strip_prefix(self.strip.as_str())?;
+                Some(format!("{}{rest}", self.add))
+            }
+            AffixKind::Suffix => {
+                if !self.condition.matches_end(stem) {
+                    return None;
+                }
+                let rest = stem.strip_suffix(self.strip.as_str())?;
+                Some(format!("{rest}{}", self.add))
+            }
+        }
+    }
+
+    /// Removes the affix from the `word`, giving back the stem it was added to, `None` if the rule can't have made the `word`.
+    pub fn unapply(&self, word: &str) -> Option<String> {
+        let stem = self.remove(word)?;
+        let matches = match self.kind {
+            AffixKind::Prefix => self.condition.matches_start(&stem),
+            AffixKind::Suffix => self.condition.matches_end(&stem),
+        };
+        matches.then_some(stem)
+    }
+
+    /// Removes the affix from the `word` without checking the condition, to guess the stem of a misspelled word.
+    pub fn remove(&self, word: &str) -> Option<String> {
+        match self.kind {
+            AffixKind::Prefix => {
+                let rest = word.strip_prefix(self.add.as_str())?;
+                Some(format!("{}{rest}", self.strip))
+            }
+            AffixKind::Suffix => {
+                let rest = word.strip_suffix(self.add.as_str())?;
+                Some(format!("{rest}{}", self.strip))
+            }
+        }
+    }
+}
+
+/// All rules with the same flag.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AffixClass {
+    pub kind: AffixKind,
+    /// Prefixes and suffixes can be combined on a word if both allow it.
+    pub cross_product: bool,
+    pub rules: Vec<AffixRule>,
+}
+
+/// Affix rules of a Hunspell `.aff` file.
+///
+/// Supports `SET` (`UTF-8` and `ISO8859-1`), `FLAG`, `AF`, `PFX`, `SFX`, `NEEDAFFIX`, `FORBIDDENWORD` and `ONLYINCOMPOUND`,
+/// other directives are ignored.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Affixes {
+    pub flag_type: FlagType,
+    /// Sets of flags that `.dic` entries can refer to by number, starting from `1`.
+    pub aliases: Vec<Vec<Flag>>,
+    pub classes: HashMap<Flag, AffixClass>,
+    /// Words with this flag are only valid with an affix.
+    pub need_affix: Option<Flag>,
+    /// Words with this flag are never valid.
+    pub forbidden: Option<Flag>,
+    /// Words with this flag are only valid inside compounds.
+    pub only_in_compound: Option<Flag>,
+}
+
+impl Affixes {
+    /// Loads the affix rules from an `.aff` `file`.
+    pub fn load(file: impl AsRef<Path>) -> io::Result<Self> {
+        let bytes = fs::read(file)?;
+        Self::parse(&decode(&bytes, encoding(&bytes))?)
+    }
+
+    /// Parses the affix rules of an `.aff` file.
+    pub fn parse(content: &str) -> io::Result<Self> {
+        let mut affixes = Self::default();
+        // The first `AF` line is the amount of aliases
+        let mut alias_count = true;
+        for (i, line) in content.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let invalid = |msg: &str| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("affix file line {}: {msg}", i + 1),
+                )
+            };
+            let args: Vec<&str> = line.split_whitespace().collect();
+            let arg = |at: usize| {
+                args.get(at)
+                    .copied()
+                    .ok_or_else(|| invalid("missing argument"))
+            };
+            let flag = |at: usize| -> io::Result<Flag> {
+                match affixes.flag_type.parse(arg(at)?).as_deref() {
+                    Some([flag]) => Ok(*flag),
+                    _ => Err(invalid(&format!("invalid flag `{}`", args[at]))),
+                }
+            };
+
+            match args[0] {
+                "FLAG" => {
+                    affixes.flag_type = match arg(1)? {
+                        "long" => FlagType::Long,
+                        "num" => FlagType::Num,
+                        // Flags are read as chars, so utf-8 flags are short ones
+                        "UTF-8" => FlagType::Short,
+                        other => return Err(invalid(&format!("unknown flag type `{other}`"))),
+                    }
+                }
+                "NEEDAFFIX" => affixes.need_affix = Some(flag(1)?),
+                "FORBIDDENWORD" => affixes.forbidden = Some(flag(1)?),
+                "ONLYINCOMPOUND" => affixes.only_in_compound = Some(flag(1)?),
+                "AF" if alias_count => alias_count = false,
+                "AF" => {
+                    let flags = affixes
+                        .flag_type
+                        .parse(arg(1)?)
+                        .ok_or_else(|| invalid(&format!("invalid flags `{}`", args[1])))?;
+                    affixes.aliases.push(flags);
+                }
+                "PFX" | "SFX" => {
+                    let kind = if args[0] == "PFX" {
+                        AffixKind::Prefix
+                    } else {
+                        AffixKind::Suffix
+                    };
+                    let flag = flag(1)?;
+                    match affixes.classes.get(&flag) {
+                        None => {
+                            let cross_product = match arg(2)? {
+                                "Y" => true,
+                                "N" => false,
+                                other => {
+                                    return Err(invalid(&format!(
+                                        "expected `Y` or `N`, got `{other}`"
+                                    )));
+                                }
+                            };
+                            affixes.classes.insert(
+                                flag,
+                                AffixClass {
+                                    kind,
+                                    cross_product,
+                                    rules: vec![],
+                                },
+                            );
+                        }
+                        Some(class) if class.kind != kind => {
+                            return Err(invalid("flag is used by both a prefix and a suffix"));
+                        }
+                        Some(_) => {
+                            let strip = match arg(2)? {
+                                "0" => "",
+                                strip => strip,
+                            };
+                            let (add, continuation) = match arg(3)?.split_once('/') {
+                                Some((add, flags)) => (
+                                    add,
+                                    affixes.parse_flags(flags).ok_or_else(|| {
+                                        invalid(&format!("invalid flags `{flags}`"))
+                                    })?,
+                                ),
+                                None => (args[3], vec![]),
+                            };
+                            let add = if add == "0" { "" } else { add };
+                            let condition = args.get(4).copied().unwrap_or(".");
+                            let condition = Condition::parse(condition).ok_or_else(|| {
+                                invalid(&format!("invalid condition `{condition}`"))
+                            })?;
+                            let rule = AffixRule {
+                                kind,
+                                strip: strip.to_owned(),
+                                add: add.to_owned(),
+                                continuation,
+                                condition,
+                            };
+                            affixes.classes.get_mut(&flag).unwrap().rules.push(rule);
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Ok(affixes)
+    }
+
+    /// Parses the flags of a word or an affix, which can be an alias number if the file has `AF` aliases.
+    pub fn parse_flags(&self, flags: &str) -> Option<Vec<Flag>> {
+        if !self.aliases.is_empty()
+            && let Ok(alias) = flags.parse::<usize>()
+        {
+            return self.aliases.get(alias.checked_sub(1)?).cloned();
+        }
+        self.flag_type.parse(flags)
+    }
+
+    /// Rules of the affix classes with the `flags` and of the `kind`, with the cross product of their class.
+    pub fn rules<'a>(
+        &'a self,
+        flags: &'a [Flag],
+        kind: AffixKind,
+    ) -> impl Iterator<Item = (&'a AffixRule, bool)> + 'a {
+        flags
+            .iter()
+            .filter_map(|flag| self.classes.get(flag))
+            .filter(move |class| class.kind == kind)
+            .flat_map(|class| class.rules.iter().map(|rule| (rule, class.cross_product)))
+    }
+
+    /// Generates all forms of the `stem` with the `flags`, the stem itself included unless it needs an affix.
+    ///
+    /// Suffixes can have one more suffix after them, and are combined with prefixes if both allow it.
+    pub fn expand(&self, stem: &str, flags: &[Flag]) -> Vec<String> {
+        let has =
+            |flags: &[Flag], flag: Option<Flag>| flag.is_some_and(|flag| flags.contains(&flag));
+        if has(flags, self.forbidden) {
+            return vec![];
+        }
+
+        let mut forms = vec![];
+        if !has(flags, self.need_affix) && !has(flags, self.only_in_compound) {
+            forms.push(stem.to_owned());
+        }
+        let mut push = |form: String, continuation: &[Flag]| {
+            if !has(continuation, self.need_affix) {
+                forms.push(form);
+            }
+        };
+
+        for (suffix, cross_product) in self.rules(flags, AffixKind::Suffix) {
+            let Some(suffixed) = suffix.apply(stem) else {
+                continue;
+            };
+            for (outer, _) in self.rules(&suffix.continuation, AffixKind::Suffix) {
+                if let Some(form) = outer.apply(&suffixed) {
+                    push(form, &outer.continuation);
+                }
+            }
+            if cross_product {
+                let prefix_flags: Vec<Flag> =
+                    flags.iter().chain(&suffix.continuation).copied().collect();
+                for (prefix, prefix_cross_product) in self.rules(&prefix_flags, AffixKind::Prefix) {
+                    if prefix_cross_product
+                        && prefix.condition.matches_start(stem)
+                        && let Some(form) = prefix.apply(&suffixed)
+                    {
+                        push(form, &prefix.continuation);
+                    }
+                }
+            }
+            push(suffixed, &suffix.continuation);
+        }
+        for (prefix, _) in self.rules(flags, AffixKind::Prefix) {
+            if let Some(form) = prefix.apply(stem) {
+                push(form, &prefix.continuation);
+            }
+        }
+        forms
+    }
+}
+
+/// A word of a `.dic` file with its flags.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Stem {
+    pub word: String,
+    pub flags: Vec<Flag>,
+}
+
+/// A Hunspell dictionary, the stems of a `.dic` file and the affix rules of its `.aff` file.
+#[derive(Debug, Clone, Default)]
+pub struct Hunspell {
+    pub affixes: Affixes,
+    pub stems: Vec<Stem>,
+}
+
+impl Hunspell {
+    /// Loads a dictionary from the `dic` and `aff` files, both in the encoding set by the `SET` directive of the `aff` file.
+    pub fn load(dic: impl AsRef<Path>, aff: impl AsRef<Path>) -> io::Result<Self> {
+        let aff = fs::read(aff)?;
+        let encoding = encoding(&aff);
+        let aff = decode(&aff, encoding)?;
+        let dic = decode(&fs::read(dic)?, encoding)?;
+        Self::parse(&dic, &aff)
+    }
+
+    /// Parses a dictionary from the content of its `.dic` and `.aff` files.
+    ///
+    /// The `.dic` file starts with the approximate amount of words, followed by a `word/flags` per line.
+    /// Anything after the first whitespace of a line (morphological fields) is ignored, and `\/` is a slash in the word.
+    pub fn parse(dic: &str, aff: &str) -> io::Result<Self> {
+        let affixes = Affixes::parse(aff)?;
+        let mut stems = vec![];
+        for (i, line) in dic.lines().enumerate() {
+            let entry = line.split_whitespace().next().unwrap_or_default();
+            if entry.is_empty() || (i == 0 && entry.parse::<usize>().is_ok()) {
+                continue;
+            }
+
+            let (word, flags) = split_entry(entry);
+            let flags = match flags {
+                Some(flags) => affixes.parse_flags(flags).ok_or_else(|| {
+                    io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!("dictionary line {}: invalid flags `{flags}`", i + 1),
+                    )
+                })?,
+                None => vec![],
+            };
+            stems.push(Stem { word, flags });
+        }
+        Ok(Self { affixes, stems })
+    }
+
+    /// Generates every word of the dictionary, sorted and without duplicates.
+    ///
+    /// The result can be passed to the `SpellCheckerBuilder`, see `add_hunspell_words()`.
+    pub fn expand(&self) -> Vec<String> {
+        let mut words: Vec<String> = self
+            .stems
+            .iter()
+            .flat_map(|stem| self.affixes.expand(&stem.word, &stem.flags))
+            .collect();
+        words.sort_unstable();
+        words.dedup();
+        words
+    }
+}
+
+/// Splits a `.dic` entry at the first unescaped `/`.
+fn split_entry(entry: &str) -> (String, Option<&str>) {
+    let mut word = String::with_capacity(entry.len());
+    let mut chars = entry.char_indices();
+    while let Some((i, ch)) = chars.next() {
+        match ch {
+            '\\' if entry[i + 1..].starts_with('/') => {
+                word.push('/');
+                chars.next();
+            }
+            '/' => return (word, Some(&entry[i + 1..])),
+            ch => word.push(ch),
+        }
+    }
+    (word, None)
+}
+
+/// Gets the encoding set by the `SET` directive of an `.aff` file, Hunspell defaults to `ISO8859-1`.
+fn encoding(aff: &[u8]) -> &str {
+    aff.split(|byte| *byte == b'\n')
+        .filter_map(|line| std::str::from_utf8(line).ok())
+        .find_map(|line| line.trim().strip_prefix("SET "))
+        .map_or("ISO8859-1", str::trim)
+}
+
+fn decode(bytes: &[u8], encoding: &str) -> io::Result<String> {
+    match encoding.to_ascii_uppercase().as_str() {
+        "UTF-8" | "UTF8" => String::from_utf8(bytes.to_vec())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidData, "file is not valid utf-8")),
+        // Every byte of latin-1 is the char with the same code point
+        "ISO8859-1" | "ISO-8859-1" => Ok(bytes.iter().map(|byte| *byte as char).collect()),
+        other => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unsupported encoding `{other}`"),
+        )),
+    }
+}
@@ -0,0 +1,182 @@
+use serde::{Deserialize, Serialize};
+
+use super::{WordBlob, group_starts, locate};
+use crate::matching::damerau_levenshtein_distance;
+
+/// Burkhard-Keller tree over the words of the dataset.
+///
+/// Each child is stored under its distance to the parent, so a lookup can skip whole subtrees using the triangle inequality.
+/// Distances are unrestricted Damerau-Levenshtein, which is a metric, and never bigger than the distances of the other `MatchAlgorithm`s.
+/// That way a lookup never misses a word any of them would match, and candidates only need to be matched again to get identical results.
+///
+/// Building is about `O(n log n)` distance calculations, which is slow for big datasets, but only needs to be done once.
+/// The tree is serializable, and can be saved with the dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BkTree {
+    /// Index of the first word of each group, words are numbered across all groups.
+    group_starts: Vec<u32>,
+    /// Nodes of the tree, the first one is the root.
+    nodes: Vec<BkNode>,
+    /// Distances are measured in chars instead of bytes.
+    #[serde(default)]
+    chars: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BkNode {
+    word: u32,
+    /// `(distance, node)` pairs.
+    children: Vec<(u32, u32)>,
+}
+
+impl BkTree {
+    /// Builds the tree over all words of the `groups`.
+    pub fn new<G: WordBlob>(groups: &[G]) -> Self {
+        Self::build(groups, false)
+    }
+
+    /// Builds the tree over all words of the `groups`, measuring distances in chars.
+    ///
+    /// Used for utf-8 words, where a single edit of a char can change several bytes.
+    pub fn over_chars<G: WordBlob>(groups: &[G]) -> Self {
+        Self::build(groups, true)
+    }
+
+    fn build<G: WordBlob>(groups: &[G], chars: bool) -> Self {
+        let mut tree = Self {
+            group_starts: group_starts(groups),
+            nodes: Vec::with_capacity(groups.iter().map(|g| g.words_count()).sum()),
+            chars,
+        };
+
+        for (gi, group) in groups.iter().enumerate() {
+            for wi in 0..group.words_count() {
+                tree.insert(groups, tree.group_starts[gi] + wi as u32, group.word(wi));
+            }
+        }
+        tree
+    }
+
+    fn insert<G: WordBlob>(&mut self, groups: &[G], id: u32, word: &[u8]) {
+        if self.nodes.is_empty() {
+            self.nodes.push(BkNode {
+                word: id,
+                children: vec![],
+            });
+            return;
+        }
+
+        let mut query = Query::new(word, self.chars);
+        let mut current = 0;
+        loop {
+            let node_word = self.word(groups, self.nodes[current].word);
+            let dist = query.distance(node_word) as u32;
+            if dist == 0 {
+                return;
+            }
+            match self.nodes[current]
+                .children
+                .iter()
+                .find(|(child_dist, _)| *child_dist == dist)
+            {
+                Some((_, child)) => current = *child as usize,
+                None => {
+                    let child = self.nodes.len() as u32;
+                    self.nodes.push(BkNode {
+                        word: id,
+                        children: vec![],
+                    });
+                    self.nodes[current].children.push((dist, child));
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Amount of words the tree was built over.
+    pub fn words_count(&self) -> usize {
+        *self.group_starts.last().unwrap_or(&0) as usize
+    }
+
+    /// Finds all words that can be at most `max_dif` away from the `word`.
+    ///
+    /// Returns `(group index, word index)` pairs, sorted in the same order as the groups.
+    ///
+    /// The tree only stores the position of the words, so `groups` have to be the same the tree was built over.
+    pub fn find<G: WordBlob>(
+        &self,
+        groups: &[G],
+        word: &[u8],
+        max_dif: usize,
+    ) -> Vec<(usize, usize)> {
+        if self.nodes.is_empty() {
+            return vec![];
+        }
+
+        let mut query = Query::new(word, self.chars);
+        let mut found = vec![];
+        let mut stack = vec![0usize];
+        while let Some(current) = stack.pop() {
+            let node = &self.nodes[current];
+            let dist = query.distance(self.word(groups, node.word));
+            if dist <= max_dif {
+                found.push(node.word);
+            }
+            let range = dist.saturating_sub(max_dif) as u32..=(dist + max_dif) as u32;
+            stack.extend(
+                node.children
+                    .iter()
+                    .filter(|(child_dist, _)| range.contains(child_dist))
+                    .map(|(_, child)| *child as usize),
+            );
+        }
+
+        found.sort_unstable();
+        found
+            .into_iter()
+            .map(|id| locate(&self.group_starts, id))
+            .collect()
+    }
+
+    fn word<'a, G: WordBlob>(&self, groups: &'a [G], id: u32) -> &'a [u8] {
+        let (group, index) = locate(&self.group_starts, id);
+        groups[group].word(index)
+    }
+}
+
+/// A word being inserted or looked up, measured against the words of the tree.
+///
+/// In chars mode the word is decoded once, and every candidate is decoded into the same buffer.
+enum Query<'w> {
+    Bytes(&'w [u8]),
+    Chars { word: Vec<char>, candidate: Vec<char> },
+}
+
+impl<'w> Query<'w> {
+    fn new(word: &'w [u8], chars: bool) -> Self {
+        if chars {
+            Self::Chars {
+                word: String::from_utf8_lossy(word).chars().collect(),
+                candidate: vec![],
+            }
+        } else {
+            Self::Bytes(word)
+        }
+    }
+
+    fn distance(&mut self, candidate_word: &[u8]) -> usize {
+        match self {
+            Self::Bytes(word) => distance(word, candidate_word),
+            Self::Chars { word, candidate } => {
+                candidate.clear();
+                candidate.extend(String::from_utf8_lossy(candidate_word).chars());
+                distance(word, candidate)
+            }
+        }
+    }
+}
+
+fn distance<T: Eq>(word: &[T], candidate: &[T]) -> usize {
+    damerau_levenshtein_distance(word, candidate, usize::MAX / 2)
+        .expect("Distance is never bigger than the limit")
+}
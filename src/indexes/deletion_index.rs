@@ -0,0 +1,261 @@
+use std::{
+    fs::File,
+    io::{self, BufReader, BufWriter, Read, Write},
+    path::Path,
+};
+
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use super::{WordBlob, group_starts, locate};
+
+const MAGIC: &[u8; 4] = b"SRDI";
+const VERSION: u32 = 1;
+
+/// SymSpell style index of deletion variants.
+///
+/// Every word of the dataset is stored under each variant of its first `prefix_len` bytes with up to `max_dif` deletions.
+/// If two words are at most `max_dif` apart, they will share at least one variant,
+/// so looking up the variants of a checked word gives every word that can be similar to it (and a few more, because of hash collisions).
+///
+/// Candidates still need to be matched with the same algorithm as the full scan, which makes the results identical.
+///
+/// The index is built over a fixed set of word groups, and has to be rebuilt when words are added or removed.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DeletionIndex {
+    max_dif: usize,
+    prefix_len: usize,
+    /// Index of the first word of each group, words are numbered across all groups.
+    group_starts: Vec<u32>,
+    /// Variants are bucketed by the top `bucket_bits` of their hash.
+    bucket_bits: u32,
+    /// Index of the first entry of each bucket.
+    buckets: Vec<u32>,
+    /// Hashes of the variants, grouped by bucket.
+    hashes: Vec<u32>,
+    /// Number of the word each hash belongs to.
+    words: Vec<u32>,
+}
+
+impl DeletionIndex {
+    /// Length of the prefix used by default, the same as SymSpell uses.
+    pub const DEFAULT_PREFIX_LEN: usize = 7;
+
+    /// Builds the index over `groups`, supporting lookups of up to `max_dif`.
+    ///
+    /// Only the first `prefix_len` bytes of each word are used to generate variants,
+    /// longer prefixes give fewer candidates per lookup, but make the index bigger.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `prefix_len` is bigger than `64`.
+    pub fn new<G: WordBlob>(groups: &[G], max_dif: usize, prefix_len: usize) -> Self {
+        assert!(
+            prefix_len <= 64,
+            "DeletionIndex prefix_len can't be bigger than 64"
+        );
+        let group_starts = group_starts(groups);
+
+        let entries: Vec<(u32, u32)> = groups
+            .par_iter()
+            .enumerate()
+            .flat_map(|(gi, group)| {
+                let start = group_starts[gi];
+                (0..group.words_count())
+                    .into_par_iter()
+                    .flat_map_iter(move |wi| {
+                        let word = group.word(wi);
+                        let prefix = &word[..word.len().min(prefix_len)];
+                        deletion_variants(prefix, max_dif)
+                            .into_iter()
+                            .map(move |hash| (hash, start + wi as u32))
+                    })
+            })
+            .collect();
+
+        // Counting sort into buckets, about one bucket per 8 entries
+        let bucket_bits = (entries.len().max(1).ilog2().saturating_sub(3)).clamp(1, 24);
+        let mut buckets = vec![0u32; (1 << bucket_bits) + 1];
+        for (hash, _) in &entries {
+            buckets[(hash >> (32 - bucket_bits)) as usize + 1] += 1;
+        }
+        for i in 1..buckets.len() {
+            buckets[i] += buckets[i - 1];
+        }
+        let mut next = buckets.clone();
+        let mut hashes = vec![0u32; entries.len()];
+        let mut words = vec![0u32; entries.len()];
+        for (hash, word) in entries {
+            let slot = &mut next[(hash >> (32 - bucket_bits)) as usize];
+            hashes[*slot as usize] = hash;
+            words[*slot as usize] = word;
+            *slot += 1;
+        }
+
+        Self {
+            max_dif,
+            prefix_len,
+            group_starts,
+            bucket_bits,
+            buckets,
+            hashes,
+            words,
+        }
+    }
+
+    /// Maximum difference the index was built for.
+    pub fn max_dif(&self) -> usize {
+        self.max_dif
+    }
+
+    /// Amount of words the index was built over.
+    pub fn words_count(&self) -> usize {
+        *self.group_starts.last().unwrap_or(&0) as usize
+    }
+
+    /// Finds all words that can be at most `max_dif` away from the `word`.
+    ///
+    /// Returns `(group index, word index)` pairs, sorted in the same order as the groups.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_dif` is bigger than the one the index was built for.
+    pub fn candidates(&self, word: &[u8], max_dif: usize) -> Vec<(usize, usize)> {
+        assert!(
+            max_dif <= self.max_dif,
+            "DeletionIndex was built for max_dif {}, got {max_dif}",
+            self.max_dif
+        );
+        let prefix = &word[..word.len().min(self.prefix_len)];
+
+        let mut found = vec![];
+        for hash in deletion_variants(prefix, max_dif) {
+            let bucket = (hash >> (32 - self.bucket_bits)) as usize;
+            let range = self.buckets[bucket] as usize..self.buckets[bucket + 1] as usize;
+            for (h, word) in self.hashes[range.clone()].iter().zip(&self.words[range]) {
+                if *h == hash {
+                    found.push(*word);
+                }
+            }
+        }
+        found.sort_unstable();
+        found.dedup();
+
+        found
+            .into_iter()
+            .map(|id| locate(&self.group_starts, id))
+            .collect()
+    }
+
+    /// Saves the index into a binary `file`.
+    pub fn save(&self, file: impl AsRef<Path>) -> io::Result<()> {
+        let mut writer = BufWriter::new(File::create(file)?);
+        writer.write_all(MAGIC)?;
+        for value in [
+            VERSION,
+            self.max_dif as u32,
+            self.prefix_len as u32,
+            self.bucket_bits,
+            self.group_starts.len() as u32,
+            self.hashes.len() as u32,
+        ] {
+            writer.write_all(&value.to_le_bytes())?;
+        }
+        for list in [&self.group_starts, &self.buckets, &self.hashes, &self.words] {
+            for value in list {
+                writer.write_all(&value.to_le_bytes())?;
+            }
+        }
+        writer.flush()
+    }
+
+    /// Loads the index saved with [`DeletionIndex::save`].
+    pub fn load(file: impl AsRef<Path>) -> io::Result<Self> {
+        let mut reader = BufReader::new(File::open(file)?);
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic != MAGIC {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "not a deletion index file",
+            ));
+        }
+
+        let mut read_u32 = || -> io::Result<u32> {
+            let mut bytes = [0u8; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        };
+        let version = read_u32()?;
+        if version != VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported deletion index version {version}"),
+            ));
+        }
+        let max_dif = read_u32()? as usize;
+        let prefix_len = read_u32()? as usize;
+        let bucket_bits = read_u32()?;
+        let groups = read_u32()? as usize;
+        let entries = read_u32()? as usize;
+        if !(1..=24).contains(&bucket_bits) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "invalid deletion index buckets",
+            ));
+        }
+        let mut read_list =
+            |len: usize| (0..len).map(|_| read_u32()).collect::<io::Result<Vec<_>>>();
+
+        Ok(Self {
+            max_dif,
+            prefix_len,
+            group_starts: read_list(groups)?,
+            bucket_bits,
+            buckets: read_list((1 << bucket_bits) + 1)?,
+            hashes: read_list(entries)?,
+            words: read_list(entries)?,
+        })
+    }
+}
+
+/// Hashes of all variants of `word` with up to `max_dif` deletions, including the `word` itself.
+fn deletion_variants(word: &[u8], max_dif: usize) -> Vec<u32> {
+    let mut variants = vec![];
+    collect_variants(word, 0, 0, max_dif, &mut variants);
+    variants.sort_unstable();
+    variants.dedup();
+    variants
+}
+
+/// Deletes each byte from `start` onwards, so every set of deleted positions is visited once.
+///
+/// Deleted positions are marked in the `deleted` bit mask, words are never longer than the prefix.
+fn collect_variants(
+    word: &[u8],
+    deleted: u64,
+    start: usize,
+    deletions: usize,
+    variants: &mut Vec<u32>,
+) {
+    let hash = word
+        .iter()
+        .enumerate()
+        .filter(|(i, _)| deleted & (1 << i) == 0)
+        .fold(FNV_OFFSET, |hash, (_, byte)| fnv1a_step(hash, *byte));
+    variants.push(hash);
+    if deletions == 0 {
+        return;
+    }
+    for i in start..word.len() {
+        collect_variants(word, deleted | (1 << i), i + 1, deletions - 1, variants);
+    }
+}
+
+const FNV_OFFSET: u32 = 0x811c9dc5;
+
+/// Step of 32 bit FNV-1a, stable between runs so the index can be saved.
+#[inline(always)]
+fn fnv1a_step(hash: u32, byte: u8) -> u32 {
+    (hash ^ byte as u32).wrapping_mul(0x01000193)
+}
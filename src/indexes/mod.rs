@@ -0,0 +1,183 @@
+mod bk_tree;
+mod deletion_index;
+mod phonetic_index;
+
+pub use bk_tree::BkTree;
+pub use deletion_index::DeletionIndex;
+pub use phonetic_index::PhoneticIndex;
+
+use std::{cmp::Reverse, collections::BinaryHeap, ops::Range};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{LenGroup, spell_checkers::simple_len_group::WordGroup};
+
+/// A blob of words of the same length, that an index can be built over.
+pub trait WordBlob: Sync {
+    /// All words of the group concatenated together, sorted.
+    fn blob(&self) -> &[u8];
+    /// Length of each word in the blob, in the elements words are matched on.
+    fn word_len(&self) -> usize;
+
+    /// Amount of words in the blob.
+    fn words_count(&self) -> usize {
+        self.blob().len().checked_div(self.word_len()).unwrap_or(0)
+    }
+
+    /// Gets the word at `index`.
+    fn word(&self, index: usize) -> &[u8] {
+        let len = self.word_len();
+        &self.blob()[index * len..(index + 1) * len]
+    }
+
+    /// Finds indexes of all words starting with `prefix` using binary search.
+    fn prefix_range(&self, prefix: &[u8]) -> Range<usize> {
+        // Cutting sorted words keeps them sorted, words shorter than the prefix never match it
+        let head = |i: usize| {
+            let word = self.word(i);
+            &word[..word.len().min(prefix.len())]
+        };
+        let start = partition_point(self.words_count(), |i| head(i) < prefix);
+        let end =
+            start + partition_point(self.words_count() - start, |i| head(start + i) == prefix);
+        start..end
+    }
+}
+
+/// `slice::partition_point` over indexes `0..len`.
+fn partition_point(len: usize, pred: impl Fn(usize) -> bool) -> usize {
+    let (mut low, mut high) = (0, len);
+    while low < high {
+        let mid = low + (high - low) / 2;
+        if pred(mid) {
+            low = mid + 1;
+        } else {
+            high = mid;
+        }
+    }
+    low
+}
+
+impl WordBlob for LenGroup {
+    fn blob(&self) -> &[u8] {
+        self.blob.as_bytes()
+    }
+
+    fn word_len(&self) -> usize {
+        self.len
+    }
+}
+
+impl WordBlob for WordGroup {
+    fn blob(&self) -> &[u8] {
+        self.blob.as_bytes()
+    }
+
+    fn word_len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Index used by the `SpellChecker` to find suggestion candidates.
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub enum SuggestionIndex {
+    /// No index, every word of the groups in `max_dif` is matched.
+    #[default]
+    Scan,
+    /// Only words sharing a deletion variant with the checked word are matched, see [`DeletionIndex`].
+    Deletion(DeletionIndex),
+    /// Only words in `max_dif` of the checked word in a [`BkTree`] are matched.
+    BkTree(BkTree),
+}
+
+impl SuggestionIndex {
+    /// Finds candidates for words at most `max_dif` away from the `word` in the `groups` the index was built over.
+    ///
+    /// Returns `(group index, word index)` pairs in the same order as the groups,
+    /// or `None` if there is no index or it can't be used for the `max_dif`.
+    pub fn candidates<G: WordBlob>(
+        &self,
+        groups: &[G],
+        word: &[u8],
+        max_dif: usize,
+    ) -> Option<Vec<(usize, usize)>> {
+        match self {
+            Self::Scan => None,
+            Self::Deletion(index) => {
+                (index.max_dif() >= max_dif).then(|| index.candidates(word, max_dif))
+            }
+            Self::BkTree(tree) => Some(tree.find(groups, word, max_dif)),
+        }
+    }
+
+    /// Amount of words the index was built over, `None` for [`SuggestionIndex::Scan`].
+    pub fn words_count(&self) -> Option<usize> {
+        match self {
+            Self::Scan => None,
+            Self::Deletion(index) => Some(index.words_count()),
+            Self::BkTree(tree) => Some(tree.words_count()),
+        }
+    }
+}
+
+/// Finds up to `limit` words starting with `prefix` across all `groups`, in lexicographic order.
+///
+/// Each group is already sorted, so their ranges of matching words are merged.
+/// Returns `(group index, word index)` pairs, `limit` of `0` returns all words.
+pub(crate) fn complete<G: WordBlob>(
+    groups: &[G],
+    prefix: &[u8],
+    limit: usize,
+) -> Vec<(usize, usize)> {
+    let mut ranges: Vec<Range<usize>> = groups.iter().map(|g| g.prefix_range(prefix)).collect();
+    let mut heap: BinaryHeap<Reverse<(&[u8], usize)>> = ranges
+        .iter()
+        .enumerate()
+        .filter(|(_, range)| !range.is_empty())
+        .map(|(gi, range)| Reverse((groups[gi].word(range.start), gi)))
+        .collect();
+
+    let mut found = vec![];
+    while let Some(Reverse((_, gi))) = heap.pop() {
+        found.push((gi, ranges[gi].start));
+        if found.len() == limit {
+            break;
+        }
+        ranges[gi].start += 1;
+        if !ranges[gi].is_empty() {
+            heap.push(Reverse((groups[gi].word(ranges[gi].start), gi)));
+        }
+    }
+    found
+}
+
+/// Same as `complete()` with the lowercase `prefix`, returning the words themselves.
+pub(crate) fn complete_words<'a, G: WordBlob>(
+    groups: &'a [G],
+    prefix: &str,
+    limit: usize,
+) -> Vec<&'a str> {
+    let prefix = prefix.to_lowercase();
+    complete(groups, prefix.as_bytes(), limit)
+        .into_iter()
+        .filter_map(|(group, index)| std::str::from_utf8(groups[group].word(index)).ok())
+        .collect()
+}
+
+/// Numbers words across all `groups`, returns the number of the first word of each group, and the total amount of words at the end.
+fn group_starts<G: WordBlob>(groups: &[G]) -> Vec<u32> {
+    let mut starts = Vec::with_capacity(groups.len() + 1);
+    let mut total = 0u32;
+    for group in groups {
+        starts.push(total);
+        total += group.words_count() as u32;
+    }
+    starts.push(total);
+    starts
+}
+
+/// Finds `(group index, word index)` of the word number `id`.
+fn locate(group_starts: &[u32], id: u32) -> (usize, usize) {
+    let group = group_starts.partition_point(|start| *start <= id) - 1;
+    (group, (id - group_starts[group]) as usize)
+}
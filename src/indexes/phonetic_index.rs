@@ -0,0 +1,74 @@
+use std::str::from_utf8;
+
+use rayon::prelude::*;
+
+use super::{WordBlob, group_starts, locate};
+use crate::phonetic::PhoneticAlgorithm;
+
+/// Index of the phonetic key of every word of the dataset.
+///
+/// Finds words that sound like the checked word, even when they are too many edits away for the other indexes.
+///
+/// The index is built over a fixed set of word groups, and has to be rebuilt when words are added or removed.
+pub struct PhoneticIndex {
+    algorithm: Box<dyn PhoneticAlgorithm>,
+    /// Index of the first word of each group, words are numbered across all groups.
+    group_starts: Vec<u32>,
+    /// `(key, word)` pairs, sorted by key.
+    entries: Vec<(Box<str>, u32)>,
+}
+
+impl PhoneticIndex {
+    /// Builds the index over `groups`, encoding each word with the `algorithm`.
+    pub fn new<G: WordBlob>(groups: &[G], algorithm: impl PhoneticAlgorithm + 'static) -> Self {
+        let group_starts = group_starts(groups);
+        let mut entries: Vec<(Box<str>, u32)> = groups
+            .par_iter()
+            .enumerate()
+            .flat_map(|(gi, group)| {
+                let start = group_starts[gi];
+                let algorithm = &algorithm;
+                (0..group.words_count())
+                    .into_par_iter()
+                    .filter_map(move |wi| {
+                        let word = from_utf8(group.word(wi)).ok()?;
+                        let key = algorithm.key(word);
+                        (!key.is_empty()).then(|| (key.into_boxed_str(), start + wi as u32))
+                    })
+            })
+            .collect();
+        entries.par_sort_unstable();
+
+        Self {
+            algorithm: Box::new(algorithm),
+            group_starts,
+            entries,
+        }
+    }
+
+    /// Amount of words the index was built over.
+    pub fn words_count(&self) -> usize {
+        *self.group_starts.last().unwrap_or(&0) as usize
+    }
+
+    /// Phonetic key of the `word`.
+    pub fn key(&self, word: &str) -> String {
+        self.algorithm.key(word)
+    }
+
+    /// Finds all words with the same phonetic key as the `word`.
+    ///
+    /// Returns `(group index, word index)` pairs, sorted in the same order as the groups.
+    pub fn find(&self, word: &str) -> Vec<(usize, usize)> {
+        let key = self.key(word);
+        if key.is_empty() {
+            return vec![];
+        }
+        let start = self.entries.partition_point(|(k, _)| **k < *key);
+        self.entries[start..]
+            .iter()
+            .take_while(|(k, _)| **k == *key)
+            .map(|(_, id)| locate(&self.group_starts, *id))
+            .collect()
+    }
+}
@@ -0,0 +1,114 @@
+/// Physical layout of a keyboard, used to find keys that are easy to hit instead of each other.
+///
+/// Companion of [`crate::Language`], each language sets the layout it's usually typed on.
+pub trait KeyboardLayout {
+    fn name() -> &'static str;
+
+    /// Rows of keys producing characters, from the number row down, without shift.
+    ///
+    /// Rows are staggered like on a standard keyboard, each one starting a bit more to the right.
+    fn rows() -> &'static [&'static str];
+
+    /// Position of the center of `key`, `(row, column)` in key widths.
+    fn position(key: char) -> Option<(usize, f32)> {
+        Self::rows().iter().enumerate().find_map(|(row, keys)| {
+            let col = keys.chars().position(|k| k == key)?;
+            Some((
+                row,
+                col as f32 + ROW_OFFSETS[row.min(ROW_OFFSETS.len() - 1)],
+            ))
+        })
+    }
+
+    /// Checks if two different keys touch each other, on the same or neighbouring rows.
+    fn are_adjacent(a: char, b: char) -> bool {
+        if a == b {
+            return false;
+        }
+        let (Some((row_a, x_a)), Some((row_b, x_b))) = (Self::position(a), Self::position(b))
+        else {
+            return false;
+        };
+        row_a.abs_diff(row_b) <= 1 && (x_a - x_b).abs() < 1.01
+    }
+
+    /// All keys touching `key`.
+    fn neighbours(key: char) -> Vec<char> {
+        Self::rows()
+            .iter()
+            .flat_map(|row| row.chars())
+            .filter(|other| Self::are_adjacent(key, *other))
+            .collect()
+    }
+
+    /// All pairs of adjacent keys, both ways.
+    fn adjacent_pairs() -> Vec<(char, char)> {
+        let keys: Vec<char> = Self::rows().iter().flat_map(|row| row.chars()).collect();
+        keys.iter()
+            .flat_map(|a| keys.iter().map(move |b| (*a, *b)))
+            .filter(|(a, b)| Self::are_adjacent(*a, *b))
+            .collect()
+    }
+}
+
+/// Offset of each row from the left edge, in key widths.
+const ROW_OFFSETS: [f32; 4] = [0., 0.5, 0.75, 1.25];
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Qwerty;
+
+impl KeyboardLayout for Qwerty {
+    fn name() -> &'static str {
+        "qwerty"
+    }
+
+    fn rows() -> &'static [&'static str] {
+        &["1234567890-=", "qwertyuiop[]", "asdfghjkl;'", "zxcvbnm,./"]
+    }
+}
+
+/// French layout.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Azerty;
+
+impl KeyboardLayout for Azerty {
+    fn name() -> &'static str {
+        "azerty"
+    }
+
+    fn rows() -> &'static [&'static str] {
+        &[
+            "&é\"'(-è_çà)=",
+            "azertyuiop^$",
+            "qsdfghjklmù*",
+            "wxcvbn,;:!",
+        ]
+    }
+}
+
+/// German layout.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Qwertz;
+
+impl KeyboardLayout for Qwertz {
+    fn name() -> &'static str {
+        "qwertz"
+    }
+
+    fn rows() -> &'static [&'static str] {
+        &["1234567890ß", "qwertzuiopü+", "asdfghjklöä#", "yxcvbnm,.-"]
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Dvorak;
+
+impl KeyboardLayout for Dvorak {
+    fn name() -> &'static str {
+        "dvorak"
+    }
+
+    fn rows() -> &'static [&'static str] {
+        &["1234567890[]", "',.pyfgcrl/=", "aoeuidhtns-", ";qjkxbmwvz"]
+    }
+}
@@ -1,4 +1,21 @@
+use crate::{CompoundRules, KeyboardLayout, PhoneticAlgorithm, TokenizerRules};
+
 pub trait Language {
+    /// Layout the language is usually typed on.
+    type Keyboard: KeyboardLayout;
+    /// Algorithm matching words that sound alike in the language.
+    type Phonetic: PhoneticAlgorithm + Default + 'static;
+
     fn name() -> &'static str;
     fn full_name() -> &'static str;
-}
\ No newline at end of file
+
+    /// Rules for checking words made of several dictionary words, `None` if the language doesn't build them.
+    fn compounds() -> Option<CompoundRules> {
+        None
+    }
+
+    /// Rules for splitting running text into words, see `SC::check_text()`.
+    fn tokenizer() -> TokenizerRules {
+        TokenizerRules::default()
+    }
+}
@@ -1,10 +1,16 @@
-#![feature(exact_div)]
-
-use std::{cmp::Ordering, path::Path, str::from_utf8_unchecked};
+use std::{
+    cmp::Ordering,
+    ffi::OsString,
+    fs, io,
+    path::{Path, PathBuf},
+    str::from_utf8,
+};
 
 use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
 
 mod load_dict;
+mod binary_dict;
 mod matching;
 pub mod spell_checkers;
 mod spell_checker;
@@ -12,18 +18,51 @@ mod language;
 mod dict_metadata;
 mod dict_creator;
 mod english;
+mod german;
+mod compound;
+mod dictionary_stack;
+mod text;
+mod text_checker;
+mod source;
+mod markup;
+mod indexes;
+mod suggestion;
+mod scorer;
+mod error_model;
+pub mod keyboard;
+pub mod phonetic;
+pub mod hunspell;
 
 pub use spell_checker::SpellChecker as SC;
 pub use language::Language;
-pub use load_dict::load_words_dict;
+pub use load_dict::{load_words_dict, save_words_dict};
+pub use binary_dict::{Blob, Frequencies, load_binary_dict, save_binary_dict};
 pub use dict_metadata::DictMetadata;
 pub use dict_creator::SpellCheckerBuilder;
 pub use english::English;
-
-static DICT_VERSION: usize = 1;
+pub use german::German;
+pub use compound::CompoundRules;
+pub use dictionary_stack::{Dictionary, DictionaryStack};
+pub use source::{Diagnostic, SourceLanguage};
+pub use markup::Markup;
+pub use text::{Misspelling, Token, TokenizerRules, identifier_parts, tokenize_code};
+pub use text_checker::TextChecker;
+pub use matching::{AutomatonState, Edit, LevenshteinAutomaton, MatchAlgorithm, edit_script};
+pub use suggestion::Suggestion;
+pub use scorer::{FrequencyScorer, Scorer};
+pub use error_model::ErrorModel;
+pub use keyboard::KeyboardLayout;
+pub use phonetic::PhoneticAlgorithm;
+pub use hunspell::Hunspell;
+pub use indexes::{BkTree, DeletionIndex, PhoneticIndex, SuggestionIndex, WordBlob};
+
+/// Version of the dictionaries of this crate, saved in their `DictMetadata`.
+pub static DICT_VERSION: usize = 2;
 
 pub enum BinarySearchWordResult {
+    /// Start and end offsets of the word.
     Found(usize, usize),
+    /// Start and end offsets the word would have if it was inserted, keeping the blob sorted.
     NotFound(usize, usize),
 }
 
@@ -40,7 +79,12 @@ pub enum Decoding {
 //     decoding: Decoding,
 // }
 
-#[derive(Debug, Clone, Copy)]
+/// Whether the `word` can be stored in a blob, words with whitespace or control chars would break the saved word list.
+fn is_storable(word: &str) -> bool {
+    !word.chars().any(|c| c.is_whitespace() || c.is_control())
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct WordId {
     pub len: usize,
     pub offset: usize,
@@ -48,20 +92,33 @@ pub struct WordId {
 
 #[derive(Debug, Clone)]
 pub struct LenGroup {
-    blob: String,
+    blob: Blob,
     len: usize,
     count: usize,
+    /// Frequency of each word in the blob, empty if the dataset has none.
+    freq: Frequencies,
 }
 
 impl LenGroup {
     pub fn empty(len: usize) -> Self {
         Self {
-            blob: String::new(),
+            blob: Blob::default(),
             len,
             count: 0,
+            freq: Frequencies::default(),
         }
     }
 
+    /// Gets the frequency of the word at `offset`, `0` if the dataset has none.
+    pub fn frequency(&self, offset: usize) -> u32 {
+        self.freq.get(offset / self.len).unwrap_or(0)
+    }
+
+    /// Checks if the words of the group are borrowed from a memory mapped file.
+    pub fn is_mapped(&self) -> bool {
+        self.blob.is_mapped()
+    }
+
     /// Checks if a word exists in the dataset.
     ///
     /// Returns true if the word exists, false otherwise.
@@ -82,6 +139,40 @@ impl LenGroup {
         }
     }
 
+    /// Inserts the lowercase `word` where it keeps the group sorted, `false` if it's already there or of another length.
+    ///
+    /// Words with whitespace or control chars are never inserted, they would break the word list once saved.
+    /// If the group has frequencies, the `word` gets `0`.
+    pub fn insert(&mut self, word: &str) -> bool {
+        if word.len() != self.len || !is_storable(word) {
+            return false;
+        }
+        let offset = match self.find_closest(word) {
+            Some(BinarySearchWordResult::Found(..)) => return false,
+            Some(BinarySearchWordResult::NotFound(offset, _)) => offset,
+            None => 0,
+        };
+        self.blob.to_mut().insert_str(offset, word);
+        if !self.freq.is_empty() {
+            self.freq.to_mut().insert(offset / self.len, 0);
+        }
+        self.count += 1;
+        true
+    }
+
+    /// Removes the `word` and its frequency from the group, `false` if it isn't there.
+    pub fn remove(&mut self, word: &str) -> bool {
+        let Some((start, end)) = self.find(word) else {
+            return false;
+        };
+        self.blob.to_mut().replace_range(start..end, "");
+        if !self.freq.is_empty() {
+            self.freq.to_mut().remove(start / self.len);
+        }
+        self.count -= 1;
+        true
+    }
+
     pub fn find_closest(&self, word: &str) -> Option<BinarySearchWordResult> {
         if self.count == 0 {
             return None;
@@ -99,10 +190,9 @@ impl LenGroup {
         // Supports both ascii and utf-8 without a problem
         let mut low = 0usize;
         let mut high = slice.len().checked_div(word.len()).unwrap();
-        let mut mid_off = 0;
         while low < high {
             let mid = low + ((high - low) / 2);
-            mid_off = mid * word.len();
+            let mid_off = mid * word.len();
             let candidate = &slice[mid_off..(mid_off + word.len())];
             match word.cmp(candidate) {
                 Ordering::Equal => {
@@ -112,7 +202,7 @@ impl LenGroup {
                 Ordering::Greater => low = mid + 1,
             }
         }
-        BinarySearchWordResult::NotFound(mid_off, mid_off + word.len())
+        BinarySearchWordResult::NotFound(low * word.len(), (low + 1) * word.len())
     }
 }
 
@@ -127,8 +217,43 @@ pub struct SpellChecker {
     ///
     /// A value of `2` (the default) means that words that are up to two `insertions`, `deletions`, or `substitutions` away are also considered similar.
     pub max_dif: usize,
-    // added_words: Vec<String>,
-    // added_words_treshhold: usize,
+    /// Sets the algorithm used to match words against the `max_dif` budget.
+    ///
+    /// [`MatchAlgorithm::Greedy`] (the default) is the fastest, [`MatchAlgorithm::Osa`] and [`MatchAlgorithm::DamerauLevenshtein`] are exact and count transpositions as one operation.
+    pub algorithm: MatchAlgorithm,
+    /// Sets the index used to find suggestion candidates.
+    ///
+    /// [`SuggestionIndex::Scan`] (the default) matches every word of the groups in `max_dif`.
+    /// Indexes give the same results, but only touch the words that can be similar.
+    pub index: SuggestionIndex,
+    /// Sets the `Scorer` used to order suggestions.
+    ///
+    /// [`FrequencyScorer`] (the default) orders them by distance first and frequency second.
+    /// [`ErrorModel`] orders them by noisy channel costs instead.
+    pub scorer: Box<dyn Scorer>,
+    /// Sets the index used to find words that sound like the checked word, see `build_phonetic_index()`.
+    ///
+    /// `None` (the default) only suggests words in `max_dif`.
+    pub phonetic: Option<PhoneticIndex>,
+    /// Added to the `scorer` score of words only found by sounding like the checked word, more than `max_dif` edits away.
+    ///
+    /// It's in the units of the `scorer`, edits for [`FrequencyScorer`] and costs for [`ErrorModel`].
+    /// `0` (the default) ranks them by their edits like any other suggestion, negative values move them up.
+    pub phonetic_penalty: f64,
+    /// Sets the amount of words added or removed after which the dictionary is saved back to its file, see `save()`.
+    ///
+    /// `20` by default, `0` only saves on `save()`.
+    pub added_words_threshold: usize,
+    /// Words added or removed since the dictionary was last saved.
+    unsaved_changes: usize,
+    /// File the dictionary was loaded from, `None` if it wasn't.
+    dict_file: Option<DictFile>,
+}
+
+/// File a dictionary is saved back to, in the format it was loaded from.
+enum DictFile {
+    Text(PathBuf),
+    Binary(PathBuf, DictMetadata),
 }
 
 impl SpellChecker {
@@ -136,58 +261,206 @@ impl SpellChecker {
     ///
     /// The `file` should be formated acording to [Dataset Fixer](https://github.com/Zefirchiky/easy-spell-checker/tree/ca505359efdc0a862d3418ae3c8b9f0418a9f25e/dataset_fixer) (see also `load_words_dict()`)
     pub fn new(file: impl AsRef<Path>) -> Self {
-        let len_groups = load_words_dict(file).unwrap();
+        let len_groups = load_words_dict(&file).unwrap();
+        Self {
+            dict_file: Some(DictFile::Text(file.as_ref().to_path_buf())),
+            ..Self::with_groups(len_groups)
+        }
+    }
+
+    /// Creates a new `SpellChecker` from a binary dictionary `file`, see `load_binary_dict()`.
+    ///
+    /// The words are borrowed from the memory mapped file instead of being read and parsed,
+    /// so it starts faster and only takes the memory of the words it touches.
+    pub fn new_binary(file: impl AsRef<Path>) -> io::Result<Self> {
+        let (meta, len_groups) = load_binary_dict(&file)?;
+        Ok(Self {
+            dict_file: Some(DictFile::Binary(file.as_ref().to_path_buf(), meta)),
+            ..Self::with_groups(len_groups)
+        })
+    }
+
+    /// Saves the words into a binary dictionary `file` with the `meta`, to be loaded with `new_binary()`.
+    pub fn save_binary(&self, file: impl AsRef<Path>, meta: &DictMetadata) -> io::Result<()> {
+        save_binary_dict(file, meta, &self.word_groups)
+    }
+
+    fn with_groups(len_groups: Vec<LenGroup>) -> Self {
         Self {
             word_groups: len_groups,
             max_dif: 2,
-            // added_words: vec![],
-            // added_words_treshhold: 20,
+            algorithm: MatchAlgorithm::default(),
+            index: SuggestionIndex::default(),
+            scorer: Box::default(),
+            phonetic: None,
+            phonetic_penalty: 0.,
+            added_words_threshold: 20,
+            unsaved_changes: 0,
+            dict_file: None,
+        }
+    }
+
+    /// Adds the `word` to its length group, keeping it sorted, `false` if it's already in the dataset.
+    ///
+    /// Groups are created up to the length of the `word` if needed.
+    /// Suggestion indexes are dropped, as they don't know about the `word`, build them again to use them.
+    /// Once `added_words_threshold` words were added or removed, the dictionary is saved back to its file, see `save()`.
+    /// If that save fails, the error is returned but the `word` stays added and counted in `unsaved_changes()`,
+    /// so adding it again returns `Ok(false)` and the next change or `save()` retries saving.
+    ///
+    /// Returns an `InvalidInput` error for words with whitespace or control chars, which can't be saved.
+    pub fn add(&mut self, word: &str) -> io::Result<bool> {
+        let word = word.to_lowercase();
+        if word.is_empty() {
+            return Ok(false);
+        }
+        if !is_storable(&word) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("words can't have whitespace or control chars: {word:?}"),
+            ));
         }
+        while self.word_groups.len() < word.len() {
+            self.word_groups.push(LenGroup::empty(self.word_groups.len() + 1));
+        }
+        if !self.word_groups[word.len() - 1].insert(&word) {
+            return Ok(false);
+        }
+        self.changed()?;
+        Ok(true)
+    }
+
+    /// Removes the `word` from the dataset, `false` if it isn't there.
+    ///
+    /// Suggestion indexes are dropped and the dictionary saved the same way as with `add()`,
+    /// the `word` stays removed even if saving fails.
+    pub fn remove(&mut self, word: &str) -> io::Result<bool> {
+        let word = word.to_lowercase();
+        let Some(group) = word
+            .len()
+            .checked_sub(1)
+            .and_then(|i| self.word_groups.get_mut(i))
+        else {
+            return Ok(false);
+        };
+        if !group.remove(&word) {
+            return Ok(false);
+        }
+        self.changed()?;
+        Ok(true)
+    }
+
+    fn changed(&mut self) -> io::Result<()> {
+        self.index = SuggestionIndex::default();
+        self.phonetic = None;
+        self.unsaved_changes += 1;
+        if self.added_words_threshold != 0
+            && self.unsaved_changes >= self.added_words_threshold
+            && self.dict_file.is_some()
+        {
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Amount of words added or removed since the dictionary was last saved.
+    pub fn unsaved_changes(&self) -> usize {
+        self.unsaved_changes
     }
 
-    pub fn add(&mut self, word: String) -> &mut Self {
-        // self.added_words.push(word);
-        // if self.added_words.len() >= self.added_words_treshhold {
-        //     self.save()
-        // }
-        let res = self.find_closest(&word);
-        if let Some((lg, BinarySearchWordResult::NotFound(o1, _))) = res {
-            let i = lg.len - 1;
-            self.word_groups
-                .get_mut(i)
-                .unwrap()
-                .blob
-                .insert_str(o1, &word); // FIXME: Inefficient, needs to move all the words after. It should also be responsibility of LenGroup
+    /// Saves the dictionary back to the file it was loaded from, in the same format.
+    ///
+    /// The dictionary is written into a temporary file next to it first, which then replaces it,
+    /// so the file is never left half written.
+    /// Words of a binary dictionary are copied into memory first, as its memory mapped file is replaced.
+    /// Returns an error if the checker wasn't loaded from a file.
+    pub fn save(&mut self) -> io::Result<()> {
+        let (file, tmp) = match &self.dict_file {
+            Some(DictFile::Text(file) | DictFile::Binary(file, _)) => {
+                let mut tmp = OsString::from(file);
+                tmp.push(".tmp");
+                (file, PathBuf::from(tmp))
+            }
+            None => {
+                return Err(io::Error::new(
+                    io::ErrorKind::NotFound,
+                    "dictionary wasn't loaded from a file",
+                ));
+            }
+        };
+        let written = match &self.dict_file {
+            Some(DictFile::Binary(_, meta)) => {
+                // Mapped files can't be replaced on Windows, words are copied out of the map so it's closed first
+                for group in &mut self.word_groups {
+                    group.blob.to_mut();
+                    group.freq.to_mut();
+                }
+                let meta = DictMetadata {
+                    words_amount: self.word_groups.iter().map(|group| group.count).sum(),
+                    ..meta.clone()
+                };
+                save_binary_dict(&tmp, &meta, &self.word_groups)
+            }
+            _ => save_words_dict(&tmp, &self.word_groups),
+        };
+        if let Err(error) = written.and_then(|_| fs::rename(&tmp, file)) {
+            let _ = fs::remove_file(&tmp);
+            return Err(error);
         }
+        self.unsaved_changes = 0;
+        Ok(())
+    }
+
+    /// Builds a `DeletionIndex` for the current `max_dif` and uses it for suggestions.
+    ///
+    /// The index has to be rebuilt after words are added.
+    pub fn build_deletion_index(&mut self) -> &mut Self {
+        self.index = SuggestionIndex::Deletion(DeletionIndex::new(
+            &self.word_groups,
+            self.max_dif,
+            DeletionIndex::DEFAULT_PREFIX_LEN,
+        ));
         self
     }
 
-    pub fn save(&mut self) {
-        // let added_words = mem::take(&mut self.added_words);
-        // for word in added_words {
-        //     let wlen = word.len();
-        //     while wlen - 1 > self.len_groups.len() {
-        //         self.len_groups.push(LenGroup::empty(self.len_groups.len() as u16));
-        //     }
-        //     if wlen - 1 == self.len_groups.len() {
-        //         self.len_groups.push(LenGroup {
-        //             blob: word,
-        //             len: wlen as u16,
-        //             count: 1,
-        //         });
-        //     } else {
-        //         self.len_groups[wlen-1].blob.push_str(&word);
-        //     }
-        // }
-
-        // for gr in self.len_groups {
-        //     gr.blob.
-        // }
+    /// Builds a `BkTree` over all words and uses it for suggestions.
+    ///
+    /// The tree works for any `max_dif`, but has to be rebuilt after words are added.
+    pub fn build_bk_tree(&mut self) -> &mut Self {
+        self.index = SuggestionIndex::BkTree(BkTree::new(&self.word_groups));
+        self
+    }
+
+    /// Builds a `PhoneticIndex` with the phonetic algorithm of the language `L`, and merges words that sound alike into suggestions.
+    ///
+    /// The index has to be rebuilt after words are added.
+    pub fn build_phonetic_index<L: Language>(&mut self) -> &mut Self {
+        self.phonetic = Some(PhoneticIndex::new(&self.word_groups, L::Phonetic::default()));
+        self
+    }
+
+    /// Loads a `DeletionIndex` saved with `DeletionIndex::save()` from `file` and uses it for suggestions.
+    ///
+    /// Returns an error if the index was built over a different amount of words.
+    pub fn load_deletion_index(&mut self, file: impl AsRef<Path>) -> io::Result<&mut Self> {
+        let index = DeletionIndex::load(file)?;
+        let words_count: usize = self.word_groups.iter().map(|lg| lg.count).sum();
+        if index.words_count() != words_count {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!(
+                    "DeletionIndex was built over {} words, dataset has {words_count}",
+                    index.words_count()
+                ),
+            ));
+        }
+        self.index = SuggestionIndex::Deletion(index);
+        Ok(self)
     }
 
     /// Gets a word from the dataset.
     pub fn get(&self, word: WordId) -> Option<&str> {
-        let lg = self.word_groups.get(word.len)?;
+        let lg = self.word_groups.get(word.len.checked_sub(1)?)?;
         if word.offset >= lg.blob.len() {
             None
         } else {
@@ -206,8 +479,8 @@ impl SpellChecker {
     pub fn get_unchecked(&self, word: WordId) -> &str {
         let lg = self
             .word_groups
-            .get(word.len)
-            .expect(&format!("LenGroup of len {} should exist", word.len));
+            .get(word.len - 1)
+            .unwrap_or_else(|| panic!("LenGroup of len {} should exist", word.len));
         &lg.blob[word.offset..word.offset + word.len]
     }
 
@@ -215,7 +488,7 @@ impl SpellChecker {
     ///
     /// Returns true if the word exists, false otherwise.
     pub fn check(&self, word: &str) -> bool {
-        let group = self.word_groups.get(word.len());
+        let group = self.group_of_len(word.len());
         match group {
             Some(lg) => lg.check(word),
             None => false,
@@ -234,113 +507,220 @@ impl SpellChecker {
     }
 
     pub fn find(&self, word: &str) -> Option<WordId> {
-        let group = self.word_groups.get(word.len())?;
+        let group = self.group_of_len(word.len())?;
         Some(WordId {
             len: group.len,
             offset: group.find(word)?.0,
         })
     }
 
-    pub fn find_closest<'a>(&self, word: &str) -> Option<(&LenGroup, BinarySearchWordResult)> {
-        let group = self.word_groups.get(word.len())?;
+    pub fn find_closest(&self, word: &str) -> Option<(&LenGroup, BinarySearchWordResult)> {
+        let group = self.group_of_len(word.len())?;
         Some((group, group.find_closest(word)?))
     }
 
+    /// Finds up to `limit` words starting with `prefix`, in lexicographic order.
+    ///
+    /// Every length group is sorted, so matching words of each group are found with binary search and merged together.
+    /// A `limit` of `0` returns all of them.
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        indexes::complete_words(&self.word_groups, prefix, limit)
+    }
+
+    /// Gets the `LenGroup` of words with `len` bytes, groups are stored starting from length `1`.
+    fn group_of_len(&self, len: usize) -> Option<&LenGroup> {
+        self.word_groups.get(len.checked_sub(1)?)
+    }
+
     /// Finds all words in the dataset that are similar to the given `word`.
     ///
-    /// Similarity is defined as the maximum number of `deletions`, `insertions`, and `substitutions` that can be done to match the two words.
+    /// Similarity is defined as the number of `deletions`, `insertions`, `substitutions` (and transpositions, depending on the `algorithm`) needed to match the two words.
     /// The maximum difference is specified by the `max_dif` field of the `SpellChecker`.
     ///
-    /// The function returns a vector of tuples, where the first element of the tuple is the similar word, and the second element is the distance between the two words.
+    /// The function returns a vector of `Suggestion`s, with the real distance between the two words and the edits that turn one into the other.
     ///
     /// The function uses a parallel iterator to search for similar words in the dataset.
     ///
-    /// The function first filters out all words that are not of the same length as the given `word`, or that have a difference greater than the maximum difference.
-    /// It then uses the `matches_single` function to check if each word is similar to the given `word`.
+    /// Only the groups of words with a length difference of at most `max_dif` are searched.
+    /// Each word of those groups is then matched with the `algorithm` of the `SpellChecker`.
     /// If a word is similar, it is added to the result vector.
     ///
+    /// If the `index` of the `SpellChecker` supports the `max_dif`, only candidates it gives are matched instead.
+    ///
     /// The function finally collects the result vector and returns it.
-    pub fn suggest_for_word(&self, word: &[u8]) -> Vec<(&str, usize)> {
+    pub fn suggest_for_word(&self, word: &[u8]) -> Vec<Suggestion<'_>> {
         let word_len = word.len();
+        if word_len == 0 {
+            return vec![];
+        }
+
+        if let Some(candidates) = self.index.candidates(&self.word_groups, word, self.max_dif) {
+            return candidates
+                .into_par_iter()
+                .filter_map(|(gi, wi)| {
+                    let group = &self.word_groups[gi];
+                    if group.len.abs_diff(word_len) > self.max_dif {
+                        return None;
+                    }
+                    self.match_candidate(word, group, wi * group.len, group.word(wi))
+                })
+                .collect();
+        }
 
-        let min_len = word_len.saturating_sub(self.max_dif - 1);
+        let min_len = word_len.saturating_sub(self.max_dif).max(1);
         let max_len = (word_len + self.max_dif).min(self.word_groups.len());
+        if min_len > max_len {
+            return vec![];
+        }
 
-        let first_char = word[0];
-        let last_char = word[word_len - 1];
-        let words = &self.word_groups[min_len..max_len];
+        let words = &self.word_groups[min_len - 1..max_len];
         words
             .par_iter()
             .filter(|group| group.count > 0)
             .flat_map(|group| {
-                let dif = group.len as isize - word_len as isize;
-                let abs_dif = dif.abs() as usize;
-
-                let max_del = dif.max(0) as usize;
-                let max_ins = (-dif).max(0) as usize;
-                let max_chg = self.max_dif - abs_dif;
-
                 group
                     .blob
                     .as_bytes()
                     .par_chunks(group.len)
-                    .filter_map(|ch| {
-                        if abs_dif == self.max_dif {
-                            if ch[0] != first_char
-                                && ch[0] != last_char
-                                && ch[ch.len() - 1] != first_char
-                                && ch[ch.len() - 1] != last_char
-                            {
-                                return None;
-                            }
-                        }
-
-                        let (is_ok, dist) =
-                            matching::matches_single(ch, word, max_del, max_ins, max_chg);
-                        if is_ok {
-                            // Dataset will always be valid, and chars are based on len group. Cant have invalid utf-8.
-                            // Trust
-                            Some((unsafe { from_utf8_unchecked(ch) }, dist))
-                        } else {
-                            None
-                        }
-                    })
+                    .enumerate()
+                    .filter_map(|(i, ch)| self.match_candidate(word, group, i * group.len, ch))
                     .collect::<Vec<_>>()
             })
             .collect()
     }
 
+    /// Matches a single `candidate` at `offset` of the `group` against the `word` with the `algorithm` and `max_dif` of the `SpellChecker`.
+    ///
+    /// Returns the `Suggestion` if the `candidate` is similar enough.
+    #[inline(always)]
+    fn match_candidate<'a>(
+        &self,
+        word: &[u8],
+        group: &LenGroup,
+        offset: usize,
+        candidate: &'a [u8],
+    ) -> Option<Suggestion<'a>> {
+        if self.algorithm == MatchAlgorithm::Greedy
+            && candidate.len().abs_diff(word.len()) == self.max_dif
+        {
+            // With the whole budget spent on the length difference, greedy matching needs at least one end to line up.
+            let first_char = word[0];
+            let last_char = word[word.len() - 1];
+            if candidate[0] != first_char
+                && candidate[0] != last_char
+                && candidate[candidate.len() - 1] != first_char
+                && candidate[candidate.len() - 1] != last_char
+            {
+                return None;
+            }
+        }
+
+        self.algorithm.distance(word, candidate, self.max_dif)?;
+        // A dataset with wrong lengths can cut words in the middle of a char, those are skipped
+        let candidate_str = from_utf8(candidate).ok()?;
+        // Greedy matching may overestimate the distance, the script is always exact
+        let edits = edit_script(word, candidate, self.algorithm);
+        Some(Suggestion {
+            word: candidate_str.into(),
+            id: WordId {
+                len: group.len,
+                offset,
+            },
+            distance: edits.len(),
+            frequency: group.frequency(offset),
+            edits,
+        })
+    }
+
+    /// Finds words that sound like the `word` with the `phonetic` index, `(group index, word index)` pairs.
+    ///
+    /// Keys shorter than two sounds match too many words to be useful, and so do words of very different lengths.
+    fn sounds_alike(&self, word: &str) -> Vec<(usize, usize)> {
+        let Some(index) = &self.phonetic else {
+            return vec![];
+        };
+        if index.key(word).len() < 2 {
+            return vec![];
+        }
+        let mut found = index.find(word);
+        found.retain(|(gi, _)| {
+            let len = self.word_groups[*gi].len;
+            len <= word.len() * 2 && word.len() <= len * 2
+        });
+        found
+    }
+
     /// Suggests words for a given `word` based on the maximum difference specified in the constructor.
     ///
     /// If the `word` is found in the dataset, returns a vector with the given `word`.
     ///
     /// If the `word` is not found in the dataset, `SpellChecker::suggest_for_word()` will be used.
+    /// Words sounding like the `word` are added from the `phonetic` index, scored by the `scorer` plus `phonetic_penalty`.
     ///
-    /// Returns the result vector, sorted by the distance, and takes the first `take_first_x` elements.
-    pub fn suggest(&self, word: &str, take_first_x: usize) -> Vec<&str> {
+    /// Returns the result vector, sorted by the `scorer` (distance and frequency by default), and takes the first `take_first_x` elements.
+    pub fn suggest(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'_>> {
         let word = word.to_lowercase();
 
-        if let Some(word) = self.find(&word) {
-            return vec![self.get_unchecked(word)];
+        if let Some(id) = self.find(&word) {
+            let frequency = self.word_groups[id.len - 1].frequency(id.offset);
+            return vec![Suggestion::exact(self.get_unchecked(id), id, frequency)];
         }
 
         let word_bytes = word.as_bytes();
         let mut result = self.suggest_for_word(word_bytes);
+        let by_edits = result.len();
+        let sounds_alike = self.sounds_alike(&word);
+
+        if !sounds_alike.is_empty() {
+            let mut found: Vec<(usize, usize)> = result
+                .iter()
+                .map(|s| (s.id.len - 1, s.id.offset / s.id.len))
+                .collect();
+            found.sort_unstable();
+            let mut extra: Vec<Suggestion> = sounds_alike
+                .iter()
+                .filter(|pos| found.binary_search(pos).is_err())
+                .filter_map(|&(gi, wi)| {
+                    let group = &self.word_groups[gi];
+                    let candidate = group.word(wi);
+                    let candidate_str = from_utf8(candidate).ok()?;
+                    let edits = edit_script(word_bytes, candidate, self.algorithm);
+                    Some(Suggestion {
+                        word: candidate_str.into(),
+                        id: WordId {
+                            len: group.len,
+                            offset: wi * group.len,
+                        },
+                        distance: edits.len(),
+                        frequency: group.frequency(wi * group.len),
+                        edits,
+                    })
+                })
+                .collect();
+            extra.sort_by_key(|suggestion| suggestion.distance);
+            result.extend(extra);
+        }
 
         if result.len() > 1 {
-            result.par_sort_unstable_by_key(|(_, dist)| *dist);
-            result.reverse();
+            let mut scored: Vec<(f64, Suggestion)> = result
+                .into_par_iter()
+                .enumerate()
+                .map(|(i, suggestion)| {
+                    let mut score = self.scorer.score(word_bytes, &suggestion);
+                    if i >= by_edits {
+                        score += self.phonetic_penalty;
+                    }
+                    (score, suggestion)
+                })
+                .collect();
+            scored.par_sort_by(|(score1, _), (score2, _)| score1.total_cmp(score2));
+            result = scored.into_iter().map(|(_, suggestion)| suggestion).collect();
         }
 
-        if take_first_x == 0 {
-            result.into_iter().map(|(word, _)| word).collect()
-        } else {
-            result
-                .into_iter()
-                .take(take_first_x)
-                .map(|(word, _)| word)
-                .collect()
+        if take_first_x != 0 {
+            result.truncate(take_first_x);
         }
+        result
     }
 
     /// Suggests words for each `word` in the given `words` vector based on the maximum difference specified in the constructor.
@@ -349,13 +729,13 @@ impl SpellChecker {
     ///
     /// If a `word` is not found in the dataset, `SpellChecker::suggest_for_word()` will be used.
     ///
-    /// Returns the result vector, sorted by the distance, and takes the first `take_first_x` elements.
+    /// Returns the result vector, sorted by the `scorer`, and takes the first `take_first_x` elements.
     ///
     pub fn batch_suggest<'a>(
         &self,
         words: &'a [&str],
         take_first_x: usize,
-    ) -> Vec<(&'a str, Vec<&str>)> {
+    ) -> Vec<(&'a str, Vec<Suggestion<'_>>)> {
         self.batch_suggest_iter(words, take_first_x).collect()
     }
 
@@ -371,7 +751,7 @@ impl SpellChecker {
     /// The `callback` function will be called for each `word` in the given `words` vector.
     pub fn batch_suggest_with<F>(&self, words: &[&str], take_first_x: usize, mut callback: F)
     where
-        F: FnMut(&str, Vec<&str>),
+        F: FnMut(&str, Vec<Suggestion<'_>>),
     {
         words.iter().for_each(move |word| {
             let suggestions = self.suggest(word, take_first_x);
@@ -392,7 +772,7 @@ impl SpellChecker {
         &self,
         words: &'a [&str],
         take_first_x: usize,
-    ) -> impl Iterator<Item = (&'a str, Vec<&str>)> {
+    ) -> impl Iterator<Item = (&'a str, Vec<Suggestion<'_>>)> {
         words
             .iter()
             .map(move |&word| (word, self.suggest(word, take_first_x)))
@@ -417,7 +797,7 @@ impl SpellChecker {
         &self,
         words: &'a [&str],
         take_first_x: usize,
-    ) -> Vec<(&'a str, Vec<&str>)> {
+    ) -> Vec<(&'a str, Vec<Suggestion<'_>>)> {
         self.batch_par_suggest_iter(words, take_first_x).collect()
     }
 
@@ -435,7 +815,7 @@ impl SpellChecker {
     /// The function is parallel, and will use all available CPU cores in parallel.
     pub fn batch_par_suggest_with<F>(&self, words: &[&str], take_first_x: usize, callback: F)
     where
-        F: FnMut(&str, Vec<&str>) + Send + Sync + Clone,
+        F: FnMut(&str, Vec<Suggestion<'_>>) + Send + Sync + Clone,
     {
         words.par_iter().for_each_with(callback, move |cb, word| {
             let suggestions = self.suggest(word, take_first_x);
@@ -456,7 +836,7 @@ impl SpellChecker {
         &self,
         words: &'a [&str],
         take_first_x: usize,
-    ) -> impl ParallelIterator<Item = (&'a str, Vec<&str>)> {
+    ) -> impl ParallelIterator<Item = (&'a str, Vec<Suggestion<'_>>)> {
         words
             .par_iter()
             .map(move |&word| (word, self.suggest(word, take_first_x)))
@@ -1,55 +1,85 @@
 use crate::LenGroup;
-use std::{fs, path::Path};
+use std::{
+    fs::{self, File},
+    io::{self, BufWriter, Write},
+    path::Path,
+};
 
 /// Loads a words dictionary from a given file.
 ///
 /// The file should be formatted as follows:
 ///
-/// 1. Every other line, starting at `0`, should contain a `length` of `words` in the next line.
-/// 2. Every other line, starting at `1`, should contain the `words` of the given `length`, concatenated together.
+/// 1. A line containing a `length` of `words` in the next line.
+/// 2. A line containing the `words` of the given `length`, concatenated together.
+/// 3. Optionally, a line starting with `#`, containing the frequency of each word in the previous line, separated by spaces.
 ///
-/// The function returns a vector of `LenGroup`, which contains the `blob of words` of the given `length`, the `length` of the words, and the `count` of words in the blob.
+/// The pattern is repeated for every length. Frequencies can be counts or any other number where higher means more common,
+/// words of groups without them have a frequency of `0`.
+///
+/// The function returns a vector of `LenGroup`, which contains the `blob of words` of the given `length`, the `length` of the words, the `count` of words in the blob and their frequencies.
 ///
 /// This function is io bound, and will take up to `4ms` on a low end hardware.
 pub fn load_words_dict<T: AsRef<Path>>(
     file: T,
 ) -> Result<Vec<LenGroup>, Box<dyn std::error::Error>> {
     let content = fs::read_to_string(file)?; // About 2 ms
-    
-    let lines: Vec<&str> = content.lines().collect();
-
-    if lines.is_empty() {
-        return Ok(vec![]);
-    }
 
-    // Find max length from the last length line (every other line, starting at 0)
-    let max_len = lines
-        .iter()
-        .step_by(2)
-        .last()
-        .and_then(|line| line.trim().parse::<usize>().ok())
-        .unwrap_or(0);
+    let mut groups: Vec<LenGroup> = vec![];
+    let mut lines = content.lines().peekable();
+    while let Some(line) = lines.next() {
+        let Ok(word_len) = line.trim().parse::<usize>() else {
+            continue;
+        };
+        let Some(blob_line) = lines.next() else {
+            break;
+        };
+        let freq = match lines.next_if(|line| line.starts_with('#')) {
+            Some(freq_line) => freq_line[1..]
+                .split_whitespace()
+                .map(|f| f.parse::<u32>())
+                .collect::<Result<Vec<_>, _>>()?,
+            None => vec![],
+        };
+        if word_len == 0 {
+            continue;
+        }
 
-    let mut group_map: Vec<Option<(String, usize)>> = vec![None; max_len];
+        let blob = blob_line.trim().to_string();
+        let count = blob.len() / word_len;
+        if !freq.is_empty() && freq.len() != count {
+            return Err(format!(
+                "group of length {word_len} has {count} words, but {} frequencies",
+                freq.len()
+            )
+            .into());
+        }
 
-    for i in (0..lines.len()).step_by(2) {
-        if let Ok(word_len) = lines[i].trim().parse::<usize>() {
-            if word_len > 0 && (word_len) <= max_len {
-                if let Some(blob_line) = lines.get(i + 1) {
-                    let blob = blob_line.trim().to_string();
-                    let count = blob.len() / word_len;
-                    group_map[(word_len) - 1] = Some((blob, count));
-                }
-            }
+        while groups.len() < word_len {
+            groups.push(LenGroup::empty(groups.len() + 1));
         }
+        groups[word_len - 1] = LenGroup {
+            blob: blob.into(),
+            len: word_len,
+            count,
+            freq: freq.into(),
+        };
     }
 
-    let mut result = Vec::with_capacity(max_len);
-    for (idx, entry) in group_map.into_iter().enumerate() {
-        let len = idx + 1;
-        let (blob, count) = entry.unwrap_or_else(|| (String::new(), 0));
-        result.push(LenGroup { blob, len, count });
-    }
+    Ok(groups)
+}
 
-    Ok(result)
+/// Saves the `groups` into a words dictionary `file`, in the format read by [`load_words_dict()`].
+pub fn save_words_dict(file: impl AsRef<Path>, groups: &[LenGroup]) -> io::Result<()> {
+    let mut writer = BufWriter::new(File::create(file)?);
+    for group in groups {
+        writeln!(writer, "{}\n{}", group.len, &*group.blob)?;
+        if !group.freq.is_empty() {
+            write!(writer, "#")?;
+            for freq in group.freq.iter() {
+                write!(writer, " {freq}")?;
+            }
+            writeln!(writer)?;
+        }
+    }
+    writer.flush()
 }
@@ -0,0 +1,350 @@
+use std::{ops::Range, path::Path};
+
+/// A markup format whose prose can be checked, see [`crate::SC::check_file()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Markup {
+    Markdown,
+    Html,
+    Latex,
+}
+
+/// Commands whose braced argument isn't prose, like labels, references and packages.
+const LATEX_NON_PROSE_ARGS: &[&str] = &[
+    "label",
+    "ref",
+    "eqref",
+    "pageref",
+    "autoref",
+    "cref",
+    "Cref",
+    "cite",
+    "citep",
+    "citet",
+    "usepackage",
+    "documentclass",
+    "includegraphics",
+    "input",
+    "include",
+    "url",
+    "href",
+    "bibliography",
+    "bibliographystyle",
+    "newcommand",
+    "renewcommand",
+    "newenvironment",
+    "end",
+];
+
+/// Environments whose content isn't prose, like math and verbatim code.
+const LATEX_NON_PROSE_ENVS: &[&str] = &[
+    "equation",
+    "equation*",
+    "align",
+    "align*",
+    "gather",
+    "gather*",
+    "multline",
+    "multline*",
+    "math",
+    "displaymath",
+    "verbatim",
+    "lstlisting",
+    "minted",
+    "tikzpicture",
+];
+
+/// HTML elements whose content isn't prose.
+const HTML_NON_PROSE_ELEMENTS: &[&str] = &["script", "style", "pre", "code"];
+
+impl Markup {
+    /// Picks the format from a file extension, like `md` or `tex`, in any case.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Some(match extension.to_ascii_lowercase().as_str() {
+            "md" | "markdown" => Self::Markdown,
+            "html" | "htm" | "xhtml" => Self::Html,
+            "tex" | "ltx" | "sty" | "cls" => Self::Latex,
+            _ => return None,
+        })
+    }
+
+    /// Picks the format from the extension of a `file`.
+    pub fn from_path(file: impl AsRef<Path>) -> Option<Self> {
+        Self::from_extension(file.as_ref().extension()?.to_str()?)
+    }
+
+    /// Byte ranges of the prose of the `source`, to check with `SC::check_ranges()`.
+    ///
+    /// - Markdown: fenced code, inline code, link targets and definitions, HTML tags and bare URLs are skipped.
+    /// - HTML: tags with their names and attributes, comments, entities, and `script`, `style`, `pre` and `code` elements are skipped.
+    /// - LaTeX: `\commands`, comments, math, verbatim environments and arguments that aren't prose, like `\label{...}`, are skipped.
+    pub fn extract(self, source: &str) -> Vec<Range<usize>> {
+        match self {
+            Self::Markdown => markdown(source),
+            Self::Html => html(source),
+            Self::Latex => latex(source),
+        }
+    }
+}
+
+/// Collects the prose of a source, between the parts that are skipped.
+#[derive(Default)]
+struct Prose {
+    ranges: Vec<Range<usize>>,
+    start: usize,
+}
+
+impl Prose {
+    fn skip(&mut self, skipped: Range<usize>) {
+        if skipped.start > self.start {
+            self.ranges.push(self.start..skipped.start);
+        }
+        self.start = self.start.max(skipped.end);
+    }
+
+    fn finish(mut self, len: usize) -> Vec<Range<usize>> {
+        self.skip(len..len);
+        self.ranges
+    }
+}
+
+fn markdown(source: &str) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut prose = Prose::default();
+    let mut i = 0;
+    while i < source.len() {
+        let rest = &source[i..];
+        if i == 0 || bytes[i - 1] == b'\n' {
+            let trimmed = rest.trim_start_matches(' ');
+            let indented = rest.len() - trimmed.len() > 3;
+            if !indented && let Some(fence) = code_fence(trimmed) {
+                let end = closing_fence(source, line_end(source, i), fence);
+                prose.skip(i..end);
+                i = end;
+                continue;
+            }
+            // `[label]: https://...`
+            if !indented
+                && trimmed.starts_with('[')
+                && trimmed
+                    .find("]:")
+                    .is_some_and(|end| !trimmed[1..end].contains(['[', ']', '\n']))
+            {
+                let end = line_end(source, i);
+                prose.skip(i..end);
+                i = end;
+                continue;
+            }
+        }
+
+        let skipped = if rest.starts_with("<!--") {
+            Some(find_end(source, i, "-->"))
+        } else if rest.starts_with('`') {
+            Some(inline_code_end(source, i))
+        } else if rest.starts_with("](") {
+            Some(group_end(source, i + 1, b'(', b')'))
+        } else if rest.starts_with("][") {
+            Some(group_end(source, i + 1, b'[', b']'))
+        } else if is_tag_start(rest) {
+            Some(find_end(source, i, ">"))
+        } else if rest.starts_with("http://")
+            || rest.starts_with("https://")
+            || rest.starts_with("www.")
+        {
+            Some(
+                rest.find(char::is_whitespace)
+                    .map_or(source.len(), |end| i + end),
+            )
+        } else {
+            None
+        };
+        match skipped {
+            Some(end) => {
+                prose.skip(i..end);
+                i = end;
+            }
+            None => i += char_len(source, i),
+        }
+    }
+    prose.finish(source.len())
+}
+
+/// The fence of a line opening a fenced code block, like ```` ``` ```` or `~~~`.
+fn code_fence(line: &str) -> Option<&str> {
+    let fence_char = line.chars().next().filter(|c| *c == '`' || *c == '~')?;
+    let len = line.len() - line.trim_start_matches(fence_char).len();
+    (len >= 3).then(|| &line[..len])
+}
+
+/// End of the line closing a code block opened with `fence`, starting from the line at `start`.
+fn closing_fence(source: &str, mut start: usize, fence: &str) -> usize {
+    while start < source.len() {
+        let end = line_end(source, start);
+        if source[start..end]
+            .trim_start_matches(' ')
+            .starts_with(fence)
+        {
+            return end;
+        }
+        start = end;
+    }
+    source.len()
+}
+
+/// End of the inline code starting with the run of backticks at `start`, or of the run if it's never closed.
+fn inline_code_end(source: &str, start: usize) -> usize {
+    let run = source[start..].len() - source[start..].trim_start_matches('`').len();
+    let mut i = start + run;
+    while let Some(found) = source[i..].find('`') {
+        let at = i + found;
+        let len = source[at..].len() - source[at..].trim_start_matches('`').len();
+        if len == run {
+            return at + len;
+        }
+        i = at + len;
+    }
+    start + run
+}
+
+fn html(source: &str) -> Vec<Range<usize>> {
+    // Same byte offsets, to find closing tags in any case
+    let lowercase = source.to_ascii_lowercase();
+    let mut prose = Prose::default();
+    let mut i = 0;
+    while i < source.len() {
+        let rest = &lowercase[i..];
+        let skipped = if rest.starts_with("<!--") {
+            Some(find_end(source, i, "-->"))
+        } else if is_tag_start(rest) {
+            let tag_end = find_end(source, i, ">");
+            let name_len = rest[1..]
+                .find(|c: char| !c.is_ascii_alphanumeric())
+                .unwrap_or(rest.len() - 1);
+            let name = &rest[1..1 + name_len];
+            if HTML_NON_PROSE_ELEMENTS.contains(&name) {
+                let closing = lowercase[tag_end..]
+                    .find(&format!("</{name}"))
+                    .map_or(source.len(), |at| tag_end + at);
+                Some(find_end(source, closing, ">"))
+            } else {
+                Some(tag_end)
+            }
+        } else if let Some(entity) = rest.strip_prefix('&') {
+            // `&amp;`, `&#39;`
+            entity
+                .find(|c: char| !c.is_ascii_alphanumeric() && c != '#')
+                .filter(|len| *len > 0 && entity[*len..].starts_with(';'))
+                .map(|len| i + len + 2)
+        } else {
+            None
+        };
+        match skipped {
+            Some(end) => {
+                prose.skip(i..end);
+                i = end;
+            }
+            None => i += char_len(source, i),
+        }
+    }
+    prose.finish(source.len())
+}
+
+fn latex(source: &str) -> Vec<Range<usize>> {
+    let bytes = source.as_bytes();
+    let mut prose = Prose::default();
+    let mut i = 0;
+    while i < source.len() {
+        let rest = &source[i..];
+        let skipped = if rest.starts_with('%') {
+            Some(line_end(source, i))
+        } else if rest.starts_with("$$") {
+            Some(find_end(source, i + 2, "$$"))
+        } else if rest.starts_with('$') {
+            Some(find_end(source, i + 1, "$"))
+        } else if rest.starts_with("\\(") {
+            Some(find_end(source, i + 2, "\\)"))
+        } else if rest.starts_with("\\[") {
+            Some(find_end(source, i + 2, "\\]"))
+        } else if let Some(command) = rest.strip_prefix('\\') {
+            let name_len = command
+                .find(|c: char| !c.is_ascii_alphabetic())
+                .unwrap_or(command.len());
+            if name_len == 0 {
+                // `\\`, `\%` and other escapes
+                Some((i + 1 + char_len(source, i + 1)).min(source.len()))
+            } else {
+                let name = &command[..name_len];
+                let mut end = i + 1 + name_len;
+                if name == "begin" {
+                    let arg_end = group_end(source, end, b'{', b'}');
+                    let env = source[end..arg_end].trim_matches(['{', '}']);
+                    end = if LATEX_NON_PROSE_ENVS.contains(&env) {
+                        find_end(source, arg_end, &format!("\\end{{{env}}}"))
+                    } else {
+                        arg_end
+                    };
+                } else if LATEX_NON_PROSE_ARGS.contains(&name) {
+                    while bytes.get(end) == Some(&b'[') {
+                        end = group_end(source, end, b'[', b']');
+                    }
+                    end = group_end(source, end, b'{', b'}');
+                }
+                Some(end)
+            }
+        } else {
+            None
+        };
+        match skipped {
+            Some(end) => {
+                prose.skip(i..end);
+                i = end;
+            }
+            None => i += char_len(source, i),
+        }
+    }
+    prose.finish(source.len())
+}
+
+/// Whether the `text` starts with an HTML tag, like `<a`, `</a` or `<!DOCTYPE`.
+fn is_tag_start(text: &str) -> bool {
+    let mut chars = text.chars();
+    chars.next() == Some('<')
+        && chars
+            .next()
+            .is_some_and(|c| c.is_ascii_alphabetic() || c == '/' || c == '!')
+}
+
+/// End of the first `pattern` from `start`, or the end of the `source` if there's none.
+fn find_end(source: &str, start: usize, pattern: &str) -> usize {
+    source[start..]
+        .find(pattern)
+        .map_or(source.len(), |at| start + at + pattern.len())
+}
+
+/// End of the group at `start` opened with `open`, with nested groups, or `start` if there's no group.
+fn group_end(source: &str, start: usize, open: u8, close: u8) -> usize {
+    let bytes = source.as_bytes();
+    if bytes.get(start) != Some(&open) {
+        return start;
+    }
+    let mut depth = 0;
+    for (i, &byte) in bytes.iter().enumerate().skip(start) {
+        if byte == open {
+            depth += 1;
+        } else if byte == close {
+            depth -= 1;
+            if depth == 0 {
+                return i + 1;
+            }
+        }
+    }
+    source.len()
+}
+
+/// End of the line at `start`, after its `\n`.
+fn line_end(source: &str, start: usize) -> usize {
+    find_end(source, start, "\n")
+}
+
+fn char_len(source: &str, i: usize) -> usize {
+    source[i..].chars().next().map_or(1, char::len_utf8)
+}
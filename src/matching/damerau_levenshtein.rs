@@ -0,0 +1,135 @@
+use std::cell::RefCell;
+
+thread_local! {
+    /// Scratch matrix reused between calls, so the hot loop over the blobs doesn't allocate per candidate.
+    static SCRATCH: RefCell<Vec<usize>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calculates the optimal string alignment distance (restricted Damerau-Levenshtein) between `word` and `candidate`.
+///
+/// Counts `deletions`, `insertions`, `substitutions` and transpositions of two adjacent elements, each as one operation.
+/// A substring can't be edited more than once, so `ca` -> `abc` is `3` operations, not `2`.
+///
+/// Returns `None` as soon as the distance is guaranteed to be greater than `max_dif`.
+pub fn osa_distance<T: Eq>(word: &[T], candidate: &[T], max_dif: usize) -> Option<usize> {
+    let wlen = word.len();
+    let clen = candidate.len();
+    if wlen.abs_diff(clen) > max_dif {
+        return None;
+    }
+
+    let width = clen + 1;
+    SCRATCH.with_borrow_mut(|rows| {
+        rows.clear();
+        rows.resize(width * 3, 0);
+        // Three rolling rows: two rows back (for transpositions), previous and current.
+        let (mut prev2, mut prev, mut cur) = (0, width, width * 2);
+        for (j, cell) in rows[prev..prev + width].iter_mut().enumerate() {
+            *cell = j;
+        }
+
+        for i in 1..=wlen {
+            rows[cur] = i;
+            let mut row_min = i;
+            for j in 1..=clen {
+                let cost = usize::from(word[i - 1] != candidate[j - 1]);
+                let mut dist = (rows[prev + j] + 1)
+                    .min(rows[cur + j - 1] + 1)
+                    .min(rows[prev + j - 1] + cost);
+                if i > 1
+                    && j > 1
+                    && word[i - 1] == candidate[j - 2]
+                    && word[i - 2] == candidate[j - 1]
+                {
+                    dist = dist.min(rows[prev2 + j - 2] + 1);
+                }
+                rows[cur + j] = dist;
+                row_min = row_min.min(dist);
+            }
+            if row_min > max_dif {
+                return None;
+            }
+            (prev2, prev, cur) = (prev, cur, prev2);
+        }
+
+        let dist = rows[prev + clen];
+        (dist <= max_dif).then_some(dist)
+    })
+}
+
+/// Calculates the unrestricted Damerau-Levenshtein distance between `word` and `candidate`.
+///
+/// Unlike [`osa_distance`], transposed elements may be edited again afterwards, so `ca` -> `abc` is `2` operations.
+/// This makes it a true metric, which is what tree based indexes rely on.
+///
+/// Returns `None` if the distance is greater than `max_dif`.
+pub fn damerau_levenshtein_distance<T: Eq>(
+    word: &[T],
+    candidate: &[T],
+    max_dif: usize,
+) -> Option<usize> {
+    let wlen = word.len();
+    let clen = candidate.len();
+    if wlen.abs_diff(clen) > max_dif {
+        return None;
+    }
+
+    // Lowrance-Wagner, with the matrix shifted by one row and column to hold the "infinity" border.
+    let width = clen + 2;
+    let inf = wlen + clen;
+    SCRATCH.with_borrow_mut(|d| {
+        d.clear();
+        d.resize(width * (wlen + 2), 0);
+        d[0] = inf;
+        for i in 0..=wlen {
+            d[(i + 1) * width] = inf;
+            d[(i + 1) * width + 1] = i;
+        }
+        for j in 0..=clen {
+            d[j + 1] = inf;
+            d[width + j + 1] = j;
+        }
+
+        // Last row where each element of `candidate` was seen in `word`.
+        let mut last_row: Vec<(&T, usize)> = Vec::new();
+        // Smallest `row_min - row` so far. A transposition from row `r` costs at least `row_min(r) + i - r - 1` at row `i`.
+        let mut best_back = 0isize;
+        for i in 1..=wlen {
+            let mut last_match_col = 0;
+            let mut row_min = i;
+            for j in 1..=clen {
+                let i1 = last_row
+                    .iter()
+                    .find(|(el, _)| **el == candidate[j - 1])
+                    .map_or(0, |(_, row)| *row);
+                let j1 = last_match_col;
+                let cost = if word[i - 1] == candidate[j - 1] {
+                    last_match_col = j;
+                    0
+                } else {
+                    1
+                };
+
+                let dist = (d[i * width + j] + cost)
+                    .min(d[(i + 1) * width + j] + 1)
+                    .min(d[i * width + j + 1] + 1)
+                    .min(d[i1 * width + j1] + (i - i1 - 1) + 1 + (j - j1 - 1));
+                d[(i + 1) * width + j + 1] = dist;
+                row_min = row_min.min(dist);
+            }
+            // Rows after this one can only get cheaper through a transposition reaching back, which is bounded by `best_back`.
+            if row_min > max_dif && best_back + i as isize > max_dif as isize {
+                return None;
+            }
+            best_back = best_back.min(row_min as isize - i as isize);
+
+            match last_row.iter_mut().find(|(el, _)| **el == word[i - 1]) {
+                Some(entry) => entry.1 = i,
+                None => last_row.push((&word[i - 1], i)),
+            }
+        }
+
+        let dist = d[(wlen + 1) * width + clen + 1];
+        (dist <= max_dif).then_some(dist)
+    })
+}
@@ -0,0 +1,207 @@
+use super::MatchAlgorithm;
+
+/// A single operation that turns the checked word into a suggestion.
+///
+/// `pos` is an index into the checked word, `candidate_pos` is an index into the suggestion.
+/// Both count the elements the words were matched on (bytes for byte based checkers).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Edit {
+    /// `candidate[candidate_pos]` is inserted before `word[pos]`.
+    Insert { pos: usize, candidate_pos: usize },
+    /// `word[pos]` is deleted.
+    Delete { pos: usize },
+    /// `word[pos]` is replaced with `candidate[candidate_pos]`.
+    Substitute { pos: usize, candidate_pos: usize },
+    /// `word[pos]` is swapped with the next element that wasn't deleted.
+    ///
+    /// `word[pos]` becomes the element after `candidate[candidate_pos]` that wasn't inserted.
+    Transpose { pos: usize, candidate_pos: usize },
+}
+
+impl Edit {
+    /// Position of the edit in the checked word.
+    pub fn pos(&self) -> usize {
+        match *self {
+            Self::Insert { pos, .. }
+            | Self::Delete { pos }
+            | Self::Substitute { pos, .. }
+            | Self::Transpose { pos, .. } => pos,
+        }
+    }
+}
+
+/// Finds the shortest list of [`Edit`]s that turns `word` into `candidate`.
+///
+/// [`MatchAlgorithm::Greedy`] and [`MatchAlgorithm::Osa`] use optimal string alignment,
+/// [`MatchAlgorithm::DamerauLevenshtein`] also allows editing between transposed elements.
+///
+/// The length of the script is the real distance between the two words, edits are ordered by position.
+pub fn edit_script<T: Eq>(word: &[T], candidate: &[T], algorithm: MatchAlgorithm) -> Vec<Edit> {
+    match algorithm {
+        MatchAlgorithm::Greedy | MatchAlgorithm::Osa => osa_edit_script(word, candidate),
+        MatchAlgorithm::DamerauLevenshtein => damerau_levenshtein_edit_script(word, candidate),
+    }
+}
+
+fn osa_edit_script<T: Eq>(word: &[T], candidate: &[T]) -> Vec<Edit> {
+    let wlen = word.len();
+    let clen = candidate.len();
+    let width = clen + 1;
+    let mut d = vec![0usize; width * (wlen + 1)];
+    for i in 0..=wlen {
+        d[i * width] = i;
+    }
+    for (j, cell) in d[..width].iter_mut().enumerate() {
+        *cell = j;
+    }
+    let is_transposition = |i: usize, j: usize| {
+        i > 1 && j > 1 && word[i - 1] == candidate[j - 2] && word[i - 2] == candidate[j - 1]
+    };
+    for i in 1..=wlen {
+        for j in 1..=clen {
+            let cost = usize::from(word[i - 1] != candidate[j - 1]);
+            let mut dist = (d[(i - 1) * width + j] + 1)
+                .min(d[i * width + j - 1] + 1)
+                .min(d[(i - 1) * width + j - 1] + cost);
+            if is_transposition(i, j) {
+                dist = dist.min(d[(i - 2) * width + j - 2] + 1);
+            }
+            d[i * width + j] = dist;
+        }
+    }
+
+    let mut edits = Vec::with_capacity(d[wlen * width + clen]);
+    let (mut i, mut j) = (wlen, clen);
+    while i > 0 || j > 0 {
+        let dist = d[i * width + j];
+        if i > 0 && j > 0 && word[i - 1] == candidate[j - 1] && d[(i - 1) * width + j - 1] == dist {
+            i -= 1;
+            j -= 1;
+        } else if is_transposition(i, j) && d[(i - 2) * width + j - 2] + 1 == dist {
+            edits.push(Edit::Transpose {
+                pos: i - 2,
+                candidate_pos: j - 2,
+            });
+            i -= 2;
+            j -= 2;
+        } else if i > 0 && j > 0 && d[(i - 1) * width + j - 1] + 1 == dist {
+            edits.push(Edit::Substitute {
+                pos: i - 1,
+                candidate_pos: j - 1,
+            });
+            i -= 1;
+            j -= 1;
+        } else if i > 0 && d[(i - 1) * width + j] + 1 == dist {
+            edits.push(Edit::Delete { pos: i - 1 });
+            i -= 1;
+        } else {
+            edits.push(Edit::Insert {
+                pos: i,
+                candidate_pos: j - 1,
+            });
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
+
+fn damerau_levenshtein_edit_script<T: Eq>(word: &[T], candidate: &[T]) -> Vec<Edit> {
+    let wlen = word.len();
+    let clen = candidate.len();
+    // Same shifted matrix as `damerau_levenshtein_distance`, cell `(i + 1, j + 1)` is the distance of `word[..i]` and `candidate[..j]`.
+    let width = clen + 2;
+    let inf = wlen + clen;
+    let mut d = vec![0usize; width * (wlen + 2)];
+    d[0] = inf;
+    for i in 0..=wlen {
+        d[(i + 1) * width] = inf;
+        d[(i + 1) * width + 1] = i;
+    }
+    for j in 0..=clen {
+        d[j + 1] = inf;
+        d[width + j + 1] = j;
+    }
+
+    // Last row in `word[..i]` holding `candidate[j - 1]`, and last column in `candidate[..j]` holding `word[i - 1]`.
+    let last_row = |i: usize, j: usize| {
+        (1..i)
+            .rev()
+            .find(|&r| word[r - 1] == candidate[j - 1])
+            .unwrap_or(0)
+    };
+    let last_col = |i: usize, j: usize| {
+        (1..j)
+            .rev()
+            .find(|&c| candidate[c - 1] == word[i - 1])
+            .unwrap_or(0)
+    };
+    let transposition = |d: &[usize], i: usize, j: usize| {
+        let (i1, j1) = (last_row(i, j), last_col(i, j));
+        (i1, j1, d[i1 * width + j1] + (i - i1 - 1) + 1 + (j - j1 - 1))
+    };
+    for i in 1..=wlen {
+        for j in 1..=clen {
+            let cost = usize::from(word[i - 1] != candidate[j - 1]);
+            let dist = (d[i * width + j] + cost)
+                .min(d[(i + 1) * width + j] + 1)
+                .min(d[i * width + j + 1] + 1)
+                .min(transposition(&d, i, j).2);
+            d[(i + 1) * width + j + 1] = dist;
+        }
+    }
+
+    let mut edits = Vec::with_capacity(d[(wlen + 1) * width + clen + 1]);
+    let (mut i, mut j) = (wlen, clen);
+    while i > 0 || j > 0 {
+        let dist = d[(i + 1) * width + j + 1];
+        if i > 0 && j > 0 && word[i - 1] == candidate[j - 1] && d[i * width + j] == dist {
+            i -= 1;
+            j -= 1;
+            continue;
+        }
+        if i > 0 && j > 0 {
+            let (i1, j1, trans_dist) = transposition(&d, i, j);
+            if i1 > 0 && j1 > 0 && trans_dist == dist {
+                // Pushed in reverse, the whole script is reversed at the end.
+                for c in (j1..j - 1).rev() {
+                    edits.push(Edit::Insert {
+                        pos: i - 1,
+                        candidate_pos: c,
+                    });
+                }
+                for p in (i1..i - 1).rev() {
+                    edits.push(Edit::Delete { pos: p });
+                }
+                edits.push(Edit::Transpose {
+                    pos: i1 - 1,
+                    candidate_pos: j1 - 1,
+                });
+                i = i1 - 1;
+                j = j1 - 1;
+                continue;
+            }
+            if d[i * width + j] + 1 == dist {
+                edits.push(Edit::Substitute {
+                    pos: i - 1,
+                    candidate_pos: j - 1,
+                });
+                i -= 1;
+                j -= 1;
+                continue;
+            }
+        }
+        if i > 0 && d[i * width + j + 1] + 1 == dist {
+            edits.push(Edit::Delete { pos: i - 1 });
+            i -= 1;
+        } else {
+            edits.push(Edit::Insert {
+                pos: i,
+                candidate_pos: j - 1,
+            });
+            j -= 1;
+        }
+    }
+    edits.reverse();
+    edits
+}
@@ -0,0 +1,71 @@
+/// Levenshtein automaton accepting every word at most `max_dif` away from a given word, with transpositions (optimal string alignment).
+///
+/// The automaton is simulated with the rows of the distance matrix, one row per consumed element.
+/// This lets it be intersected with a trie, moving both one element at a time,
+/// and dropping a whole branch as soon as the state can't match anymore.
+pub struct LevenshteinAutomaton<'a, T> {
+    word: &'a [T],
+    max_dif: usize,
+}
+
+/// State of a [`LevenshteinAutomaton`] after consuming some elements.
+#[derive(Debug, Clone)]
+pub struct AutomatonState<T> {
+    row: Vec<usize>,
+    /// Row before the last consumed element, needed for transpositions.
+    prev_row: Vec<usize>,
+    last: Option<T>,
+}
+
+impl<'a, T: Eq + Copy> LevenshteinAutomaton<'a, T> {
+    pub fn new(word: &'a [T], max_dif: usize) -> Self {
+        Self { word, max_dif }
+    }
+
+    /// State before consuming anything.
+    pub fn start(&self) -> AutomatonState<T> {
+        AutomatonState {
+            row: (0..=self.word.len()).collect(),
+            prev_row: vec![],
+            last: None,
+        }
+    }
+
+    /// Consumes one element of a candidate.
+    pub fn step(&self, state: &AutomatonState<T>, el: T) -> AutomatonState<T> {
+        let word = self.word;
+        let mut row = Vec::with_capacity(state.row.len());
+        row.push(state.row[0] + 1);
+        for j in 1..=word.len() {
+            let cost = usize::from(word[j - 1] != el);
+            let mut dist = (state.row[j] + 1)
+                .min(row[j - 1] + 1)
+                .min(state.row[j - 1] + cost);
+            if j > 1 && state.last == Some(word[j - 1]) && word[j - 2] == el {
+                dist = dist.min(state.prev_row[j - 2] + 1);
+            }
+            row.push(dist);
+        }
+        AutomatonState {
+            row,
+            prev_row: state.row.clone(),
+            last: Some(el),
+        }
+    }
+
+    /// Returns the distance if the consumed candidate is accepted.
+    pub fn distance(&self, state: &AutomatonState<T>) -> Option<usize> {
+        let dist = state.row[self.word.len()];
+        (dist <= self.max_dif).then_some(dist)
+    }
+
+    /// Returns `false` if no continuation of the consumed candidate can be accepted.
+    pub fn can_match(&self, state: &AutomatonState<T>) -> bool {
+        // Every cell only grows from the previous row, transpositions included
+        state
+            .row
+            .iter()
+            .min()
+            .is_some_and(|min| *min <= self.max_dif)
+    }
+}
@@ -2,12 +2,15 @@
 
 /// Checks if a word matches a given candidate with at most the given maximum amount of `deletions`, `insertions` and `substitution`.
 ///
-/// Returns a tuple of `(bool, u16)` where the boolean is `true` if the word matches the candidate, and the `u16` is the total number of operations done to match the two words.
+/// Returns a tuple of `(bool, usize)` where the boolean is `true` if the word matches the candidate, and the `usize` is the total number of operations done to match the two words.
 ///
 /// The algorithm first finds the matching prefix of the two words using `SIMD` if available, and then continues with a scalar algorithm from the mismatch point.
 ///
 /// The maximum amount of `deletions`, `insertions` and `substitutions` are given as mutable parameters, and are decreased by one each time an operation is done.
 ///
+/// This is a greedy scan, it commits to the first operation that fits and never backtracks.
+/// The number of operations is an upper bound of the real distance, use [`super::osa_distance`] or [`super::damerau_levenshtein_distance`] when it has to be exact.
+///
 /// If the word matches the candidate with at most the given maximum amount of operations, the function returns true and the total number of operations done.
 /// Otherwise, it returns `false` and `0`.
 #[inline(always)]
@@ -20,6 +23,7 @@ pub fn matches_single<T: Eq>(
 ) -> (bool, usize) {
     let wlen = word.len();
     let clen = candidate.len();
+    let budget = max_deletions + max_insertions + max_substitutions;
 
     let mut wi = 0;
     let mut ci = 0;
@@ -63,11 +67,10 @@ pub fn matches_single<T: Eq>(
     let remaining_candidate = clen - ci;
 
     if remaining_word <= max_deletions && remaining_candidate <= max_insertions {
-        (
-            true,
-            max_deletions - remaining_word + max_insertions - remaining_candidate
-                + max_substitutions,
-        )
+        let remaining_budget = max_deletions - remaining_word + max_insertions
+            - remaining_candidate
+            + max_substitutions;
+        (true, budget - remaining_budget)
     } else {
         (false, 0)
     }
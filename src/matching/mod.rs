@@ -1,4 +1,57 @@
 // mod simd_find_matching_prefix;
+mod damerau_levenshtein;
+mod edit_script;
+mod levenshtein_automaton;
 mod match_word;
 
+use serde::{Deserialize, Serialize};
+
+pub use damerau_levenshtein::{damerau_levenshtein_distance, osa_distance};
+pub use edit_script::{Edit, edit_script};
+pub use levenshtein_automaton::{AutomatonState, LevenshteinAutomaton};
 pub use match_word::matches_single;
+
+/// Algorithm used to decide if a candidate is close enough to a word, and how far away it is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatchAlgorithm {
+    /// Single greedy pass of [`matches_single`].
+    ///
+    /// The fastest one, but misses transpositions and some valid matches, and the distance may be overestimated.
+    #[default]
+    Greedy,
+    /// Exact optimal string alignment distance, see [`osa_distance`].
+    Osa,
+    /// Exact unrestricted Damerau-Levenshtein distance, see [`damerau_levenshtein_distance`].
+    DamerauLevenshtein,
+}
+
+impl MatchAlgorithm {
+    /// Returns the distance between `word` and `candidate` if it's at most `max_dif`.
+    ///
+    /// For [`MatchAlgorithm::Greedy`] the `max_dif` budget is split the same way `SpellChecker::suggest_for_word` always did:
+    /// length difference goes to `deletions` or `insertions`, and the rest to `substitutions`.
+    #[inline(always)]
+    pub fn distance<T: Eq>(&self, word: &[T], candidate: &[T], max_dif: usize) -> Option<usize> {
+        match self {
+            Self::Greedy => {
+                let dif = candidate.len() as isize - word.len() as isize;
+                let abs_dif = dif.unsigned_abs();
+                if abs_dif > max_dif {
+                    return None;
+                }
+                let max_del = dif.max(0) as usize;
+                let max_ins = (-dif).max(0) as usize;
+                let (is_ok, dist) =
+                    matches_single(candidate, word, max_del, max_ins, max_dif - abs_dif);
+                is_ok.then_some(dist)
+            }
+            Self::Osa => osa_distance(word, candidate, max_dif),
+            Self::DamerauLevenshtein => damerau_levenshtein_distance(word, candidate, max_dif),
+        }
+    }
+
+    /// Returns `true` if the algorithm always finds the real distance.
+    pub fn is_exact(&self) -> bool {
+        !matches!(self, Self::Greedy)
+    }
+}
@@ -0,0 +1,144 @@
+/// Encodes words into keys shared by words that sound alike.
+///
+/// Companion of [`crate::Language`], each language sets the algorithm that fits its pronunciation.
+pub trait PhoneticAlgorithm: Send + Sync {
+    /// Encodes the `word` into its phonetic key, empty if it has nothing to encode.
+    fn key(&self, word: &str) -> String;
+}
+
+/// Classic Soundex, the first letter followed by three digits for the consonants after it.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Soundex;
+
+impl Soundex {
+    fn code(ch: u8) -> u8 {
+        match ch {
+            b'B' | b'F' | b'P' | b'V' => b'1',
+            b'C' | b'G' | b'J' | b'K' | b'Q' | b'S' | b'X' | b'Z' => b'2',
+            b'D' | b'T' => b'3',
+            b'L' => b'4',
+            b'M' | b'N' => b'5',
+            b'R' => b'6',
+            // Vowels separate consonants with the same code, `H` and `W` don't
+            b'H' | b'W' => b'-',
+            _ => b'0',
+        }
+    }
+}
+
+impl PhoneticAlgorithm for Soundex {
+    fn key(&self, word: &str) -> String {
+        let mut letters = word
+            .bytes()
+            .filter(u8::is_ascii_alphabetic)
+            .map(|ch| ch.to_ascii_uppercase());
+        let Some(first) = letters.next() else {
+            return String::new();
+        };
+
+        let mut key = vec![first];
+        let mut last = Self::code(first);
+        for ch in letters {
+            let code = Self::code(ch);
+            match code {
+                b'-' => continue,
+                b'0' => {}
+                _ if code != last => key.push(code),
+                _ => {}
+            }
+            last = code;
+            if key.len() == 4 {
+                break;
+            }
+        }
+        key.resize(4, b'0');
+        String::from_utf8(key).unwrap()
+    }
+}
+
+/// Original Metaphone by Lawrence Philips, made for English.
+///
+/// Keys are consonant sounds, `0` standing for `th` and `X` for `sh`, vowels are only kept at the start.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Metaphone;
+
+impl PhoneticAlgorithm for Metaphone {
+    fn key(&self, word: &str) -> String {
+        let w: Vec<u8> = word
+            .bytes()
+            .filter(u8::is_ascii_alphabetic)
+            .map(|ch| ch.to_ascii_uppercase())
+            .collect();
+        let is_vowel = |i: usize| matches!(w.get(i), Some(b'A' | b'E' | b'I' | b'O' | b'U'));
+        let at = |i: usize| w.get(i).copied().unwrap_or(0);
+        let next_is = |i: usize, chars: &[u8]| chars.contains(&at(i + 1));
+
+        let mut key = String::new();
+        let mut start = 0;
+        match (at(0), at(1)) {
+            (b'A', b'E') | (b'G' | b'K' | b'P', b'N') | (b'W', b'R') => start = 1,
+            (b'X', _) => {
+                key.push('S');
+                start = 1;
+            }
+            (b'W', b'H') => {
+                key.push('W');
+                start = 2;
+            }
+            _ => {}
+        }
+
+        for i in start..w.len() {
+            let ch = w[i];
+            // Doubled letters sound as one, except `cc` in `accent`
+            if i > start && ch == w[i - 1] && ch != b'C' {
+                continue;
+            }
+            match ch {
+                b'A' | b'E' | b'I' | b'O' | b'U' if i == start && key.is_empty() => {
+                    key.push(ch as char)
+                }
+                b'A' | b'E' | b'I' | b'O' | b'U' => {}
+                b'B' if i > 0 && at(i - 1) == b'M' && i + 1 == w.len() => {}
+                b'C' if next_is(i, b"H") && i > 0 && at(i - 1) == b'S' => key.push('K'),
+                b'C' if next_is(i, b"H") || (next_is(i, b"I") && at(i + 2) == b'A') => {
+                    key.push('X')
+                }
+                b'C' if next_is(i, b"EIY") => {
+                    if i == 0 || at(i - 1) != b'S' {
+                        key.push('S')
+                    }
+                }
+                b'C' => key.push('K'),
+                b'D' if next_is(i, b"G") && matches!(at(i + 2), b'E' | b'I' | b'Y') => {
+                    key.push('J')
+                }
+                b'D' => key.push('T'),
+                b'G' if next_is(i, b"H") && i + 2 < w.len() && !is_vowel(i + 2) => {}
+                b'G' if next_is(i, b"N") && (i + 2 == w.len() || &w[i + 2..] == b"ED") => {}
+                b'G' if i > 0 && at(i - 1) == b'D' && next_is(i, b"EIY") => {}
+                b'G' if next_is(i, b"EIY") => key.push('J'),
+                b'G' => key.push('K'),
+                b'H' if i > 0 && matches!(at(i - 1), b'C' | b'S' | b'P' | b'T' | b'G') => {}
+                b'H' if !is_vowel(i + 1) => {}
+                b'K' if i > 0 && at(i - 1) == b'C' => {}
+                b'P' if next_is(i, b"H") => key.push('F'),
+                b'Q' => key.push('K'),
+                b'S' if next_is(i, b"H")
+                    || (next_is(i, b"I") && matches!(at(i + 2), b'O' | b'A')) =>
+                {
+                    key.push('X')
+                }
+                b'T' if next_is(i, b"I") && matches!(at(i + 2), b'O' | b'A') => key.push('X'),
+                b'T' if next_is(i, b"H") => key.push('0'),
+                b'T' if next_is(i, b"C") && at(i + 2) == b'H' => {}
+                b'V' => key.push('F'),
+                b'W' | b'Y' if !is_vowel(i + 1) => {}
+                b'X' => key.push_str("KS"),
+                b'Z' => key.push('S'),
+                _ => key.push(ch as char),
+            }
+        }
+        key
+    }
+}
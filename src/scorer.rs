@@ -0,0 +1,36 @@
+use crate::Suggestion;
+
+/// Ranks suggestions of the `SpellChecker`.
+///
+/// Suggestions with lower scores are shown first, suggestions with equal scores keep the order they were found in.
+///
+/// Any `Fn(&[u8], &Suggestion) -> f64` closure is a `Scorer`.
+pub trait Scorer: Send + Sync {
+    /// Scores a `suggestion` for the checked `word`.
+    fn score(&self, word: &[u8], suggestion: &Suggestion) -> f64;
+}
+
+impl<F: Fn(&[u8], &Suggestion) -> f64 + Send + Sync> Scorer for F {
+    fn score(&self, word: &[u8], suggestion: &Suggestion) -> f64 {
+        self(word, suggestion)
+    }
+}
+
+/// Default `Scorer`, orders suggestions by distance first and frequency second.
+///
+/// Without frequencies in the dataset, suggestions are only ordered by distance.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct FrequencyScorer;
+
+impl Scorer for FrequencyScorer {
+    fn score(&self, _word: &[u8], suggestion: &Suggestion) -> f64 {
+        // Frequency is mapped into `[0, 1)`, so it never outweighs a single edit
+        suggestion.distance as f64 - suggestion.frequency as f64 / (u32::MAX as f64 + 1.)
+    }
+}
+
+impl Default for Box<dyn Scorer> {
+    fn default() -> Self {
+        Box::new(FrequencyScorer)
+    }
+}
@@ -0,0 +1,307 @@
+use std::{
+    fmt,
+    ops::Range,
+    path::{Path, PathBuf},
+};
+
+use crate::{Misspelling, Suggestion};
+
+/// A programming language whose comments and string literals can be checked, see [`crate::SC::check_file()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceLanguage {
+    Rust,
+    Python,
+    /// JavaScript and TypeScript.
+    JavaScript,
+    /// C and C++.
+    C,
+    Go,
+}
+
+/// How comments and string literals of a language are written.
+struct Syntax {
+    line_comments: &'static [&'static str],
+    block_comment: Option<(&'static str, &'static str)>,
+    nested_comments: bool,
+    /// Quotes of strings with escapes, longer ones first.
+    strings: &'static [&'static str],
+    /// Quotes of strings without escapes, like Go's backticks.
+    raw_strings: &'static [&'static str],
+    /// Whether `'` starts a char literal, or a lifetime, instead of a string.
+    char_literals: bool,
+    /// Whether strings can be raw like Rust's `r#"..."#`.
+    hashed_raw_strings: bool,
+}
+
+impl SourceLanguage {
+    /// Picks the language from a file extension, like `rs` or `tsx`, in any case.
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        Some(match extension.to_ascii_lowercase().as_str() {
+            "rs" => Self::Rust,
+            "py" | "pyi" => Self::Python,
+            "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "mts" | "cts" => Self::JavaScript,
+            "c" | "h" | "cc" | "cpp" | "cxx" | "hh" | "hpp" | "hxx" => Self::C,
+            "go" => Self::Go,
+            _ => return None,
+        })
+    }
+
+    /// Picks the language from the extension of a `file`.
+    pub fn from_path(file: impl AsRef<Path>) -> Option<Self> {
+        Self::from_extension(file.as_ref().extension()?.to_str()?)
+    }
+
+    fn syntax(self) -> Syntax {
+        let c_like = Syntax {
+            line_comments: &["//"],
+            block_comment: Some(("/*", "*/")),
+            nested_comments: false,
+            strings: &["\""],
+            raw_strings: &[],
+            char_literals: true,
+            hashed_raw_strings: false,
+        };
+        match self {
+            Self::Rust => Syntax {
+                nested_comments: true,
+                hashed_raw_strings: true,
+                ..c_like
+            },
+            Self::Python => Syntax {
+                line_comments: &["#"],
+                block_comment: None,
+                strings: &["\"\"\"", "'''", "\"", "'"],
+                char_literals: false,
+                ..c_like
+            },
+            Self::JavaScript => Syntax {
+                strings: &["\"", "'", "`"],
+                char_literals: false,
+                ..c_like
+            },
+            Self::C => c_like,
+            Self::Go => Syntax {
+                raw_strings: &["`"],
+                ..c_like
+            },
+        }
+    }
+
+    /// Byte ranges of the text of comments, doc comments and string literals of the `source`, to check with `SC::check_ranges()`.
+    ///
+    /// Delimiters are left out, and strings are split around escapes, so `\n` doesn't stick to the next word.
+    /// It's a lightweight lexer, unterminated comments and strings run to the end of the `source`, or of the line for one line strings.
+    pub fn extract(self, source: &str) -> Vec<Range<usize>> {
+        let syntax = self.syntax();
+        let bytes = source.as_bytes();
+        let mut ranges = vec![];
+        let mut i = 0;
+        while i < source.len() {
+            let rest = &source[i..];
+            if let Some(comment) = syntax.line_comments.iter().find(|c| rest.starts_with(**c)) {
+                let start = i + comment.len();
+                let end = source[start..]
+                    .find('\n')
+                    .map_or(source.len(), |end| start + end);
+                ranges.push(start..end);
+                i = end;
+            } else if let Some((open, close)) = syntax.block_comment
+                && rest.starts_with(open)
+            {
+                i = block_comment(source, i, open, close, syntax.nested_comments, &mut ranges);
+            } else if syntax.hashed_raw_strings
+                && let Some(hashes) = raw_string_hashes(source, i)
+            {
+                let start = i + hashes + 2;
+                let close = format!("\"{}", "#".repeat(hashes));
+                let end = source[start..]
+                    .find(&close)
+                    .map_or(source.len(), |end| start + end);
+                ranges.push(start..end);
+                i = (end + close.len()).min(source.len());
+            } else if let Some(quote) = syntax.raw_strings.iter().find(|q| rest.starts_with(**q)) {
+                let start = i + quote.len();
+                let end = source[start..]
+                    .find(quote)
+                    .map_or(source.len(), |end| start + end);
+                ranges.push(start..end);
+                i = (end + quote.len()).min(source.len());
+            } else if let Some(quote) = syntax.strings.iter().find(|q| rest.starts_with(**q)) {
+                i = string(source, i, quote, &mut ranges);
+            } else if syntax.char_literals && bytes[i] == b'\'' {
+                i = char_literal_end(source, i);
+            } else {
+                i += char_len(source, i);
+            }
+        }
+        ranges.retain(|range| !range.is_empty());
+        ranges
+    }
+}
+
+/// Pushes the text of the block comment at `start`, returns the end of it.
+fn block_comment(
+    source: &str,
+    start: usize,
+    open: &str,
+    close: &str,
+    nested: bool,
+    ranges: &mut Vec<Range<usize>>,
+) -> usize {
+    let text_start = start + open.len();
+    let mut depth = 1;
+    let mut i = text_start;
+    while i < source.len() {
+        let rest = &source[i..];
+        if nested && rest.starts_with(open) {
+            depth += 1;
+            i += open.len();
+        } else if rest.starts_with(close) {
+            depth -= 1;
+            if depth == 0 {
+                ranges.push(text_start..i);
+                return i + close.len();
+            }
+            i += close.len();
+        } else {
+            i += char_len(source, i);
+        }
+    }
+    ranges.push(text_start..source.len());
+    source.len()
+}
+
+/// Pushes the text of the string at `start` between escapes, returns the end of it.
+fn string(source: &str, start: usize, quote: &str, ranges: &mut Vec<Range<usize>>) -> usize {
+    // Triple quoted and template strings can span lines
+    let one_line = quote.len() == 1 && quote != "`";
+    let mut piece_start = start + quote.len();
+    let mut i = piece_start;
+    while i < source.len() {
+        let rest = &source[i..];
+        if rest.starts_with('\\') {
+            ranges.push(piece_start..i);
+            i += 1;
+            if i < source.len() {
+                i += char_len(source, i);
+            }
+            piece_start = i;
+        } else if rest.starts_with(quote) {
+            ranges.push(piece_start..i);
+            return i + quote.len();
+        } else if one_line && rest.starts_with('\n') {
+            break;
+        } else {
+            i += char_len(source, i);
+        }
+    }
+    ranges.push(piece_start..i);
+    i
+}
+
+/// Amount of `#` of the Rust raw string starting at `start`, like `r#"`, `None` if it isn't one.
+fn raw_string_hashes(source: &str, start: usize) -> Option<usize> {
+    let bytes = source.as_bytes();
+    // Raw strings can be byte or C strings, but `r` can't end an identifier
+    let prev = start.checked_sub(1).map(|prev| bytes[prev]);
+    let prefix_ok = match prev {
+        Some(b'b' | b'c') => start < 2 || !is_ident_byte(bytes[start - 2]),
+        Some(prev) => !is_ident_byte(prev),
+        None => true,
+    };
+    if bytes[start] != b'r' || !prefix_ok {
+        return None;
+    }
+    let hashes = bytes[start + 1..]
+        .iter()
+        .take_while(|b| **b == b'#')
+        .count();
+    (bytes.get(start + 1 + hashes) == Some(&b'"')).then_some(hashes)
+}
+
+/// End of the char literal at `start`, or just after the `'` of a lifetime.
+fn char_literal_end(source: &str, start: usize) -> usize {
+    let rest = &source[start + 1..];
+    if let Some(escaped) = rest.strip_prefix('\\') {
+        return escaped
+            .find('\'')
+            .map_or(source.len(), |end| start + end + 3);
+    }
+    match rest.chars().next() {
+        Some(c) if rest[c.len_utf8()..].starts_with('\'') => start + c.len_utf8() + 2,
+        _ => start + 1,
+    }
+}
+
+fn is_ident_byte(byte: u8) -> bool {
+    byte.is_ascii_alphanumeric() || byte == b'_'
+}
+
+fn char_len(source: &str, i: usize) -> usize {
+    source[i..].chars().next().map_or(1, char::len_utf8)
+}
+
+/// A misspelled word of a file, with its position, see [`crate::SC::check_file()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic<'s> {
+    pub file: PathBuf,
+    /// Line of the word, from 1.
+    pub line: usize,
+    /// Column of the word in chars, from 1.
+    pub column: usize,
+    pub word: String,
+    pub suggestions: Vec<Suggestion<'s>>,
+}
+
+impl<'s> Diagnostic<'s> {
+    /// Turns the `misspellings` of the `source` of a `file` into diagnostics.
+    ///
+    /// The `misspellings` have to be in the order of the `source`.
+    pub fn from_misspellings(
+        file: impl AsRef<Path>,
+        source: &str,
+        misspellings: Vec<Misspelling<'_, 's>>,
+    ) -> Vec<Self> {
+        let file = file.as_ref();
+        let mut line = 1;
+        let mut line_start = 0;
+        let mut last_end = 0;
+        misspellings
+            .into_iter()
+            .map(|misspelling| {
+                let start = misspelling.span.start;
+                for (at, _) in source[last_end..start].match_indices('\n') {
+                    line += 1;
+                    line_start = last_end + at + 1;
+                }
+                last_end = start;
+                Diagnostic {
+                    file: file.to_owned(),
+                    line,
+                    column: source[line_start..start].chars().count() + 1,
+                    word: misspelling.word.to_owned(),
+                    suggestions: misspelling.suggestions,
+                }
+            })
+            .collect()
+    }
+}
+
+/// Writes `file:line:column: word`, followed by its suggestions.
+impl fmt::Display for Diagnostic<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{}:{}: {}",
+            self.file.display(),
+            self.line,
+            self.column,
+            self.word
+        )?;
+        if !self.suggestions.is_empty() {
+            let suggestions: Vec<&str> = self.suggestions.iter().map(|s| &*s.word).collect();
+            write!(f, " => {}", suggestions.join(" "))?;
+        }
+        Ok(())
+    }
+}
@@ -1,41 +1,501 @@
-use std::marker::PhantomData;
+use std::{collections::{HashMap, HashSet}, io, marker::PhantomData, ops::Range, path::Path};
 
 use filess::{Json, ModelFileTrait};
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{DictMetadata, Language, spell_checkers::{SpellCheckerTrait, ascii, normalized, utf8}};
+use crate::{DICT_VERSION, CompoundRules, Diagnostic, DictMetadata, ErrorModel, Language, MatchAlgorithm, Misspelling, Scorer, Suggestion, TextChecker, WordId, binary_dict::{load_groups, save_groups}, spell_checkers::{SpellCheckerTrait, affix, ascii, normalized::{self, normalize}, trie, utf8}, edit_script};
+
+/// Most parts of a compound corrected by `suggest_for_word()`, each one is a lookup in every sub-checker.
+const MAX_UNKNOWN_PARTS: usize = 4;
 
 #[derive(Serialize, Deserialize)]
+#[serde(bound = "")]
 pub struct SpellChecker<L: Language> {
     pub(crate) dict_meta: DictMetadata,
     pub(crate) ascii_checker: Option<ascii::SpellChecker>,
     pub(crate) norm_checker: Option<normalized::SpellChecker>,
     pub(crate) utf8_checker: Option<utf8::SpellChecker>,
-    pub(crate) language: PhantomData<L>,
+    #[serde(default)]
+    pub(crate) trie_checker: Option<trie::SpellChecker>,
+    #[serde(default)]
+    pub(crate) affix_checker: Option<affix::SpellChecker>,
+    /// Lowercase words that are never correct nor suggested, with their preferred replacement.
+    #[serde(default)]
+    pub(crate) forbidden: HashMap<String, Option<String>>,
+    /// Only marks the language, so the checker is `Sync` whatever `L` is.
+    pub(crate) language: PhantomData<fn() -> L>,
+    /// Sets the maximum difference between words to be considered similar, `2` by default.
+    ///
+    /// Not saved with the dictionary.
+    #[serde(skip, default = "default_max_dif")]
+    pub max_dif: usize,
+    /// Sets the `Scorer` used to order suggestions, [`crate::ErrorModel::for_language()`] by default.
+    ///
+    /// Not saved with the dictionary.
+    #[serde(skip, default = "default_scorer::<L>")]
+    pub scorer: Box<dyn Scorer>,
+    /// Sets the rules for checking compound words, the ones of the language by default, `None` to turn compounds off.
+    ///
+    /// Not saved with the dictionary.
+    #[serde(skip, default = "L::compounds")]
+    pub compounds: Option<CompoundRules>,
+}
+
+/// Only the version of a saved dictionary, read when the whole of it can't be.
+#[derive(Deserialize)]
+struct VersionProbe {
+    dict_meta: VersionMeta,
+}
+
+#[derive(Deserialize)]
+struct VersionMeta {
+    version: usize,
+}
+
+pub(crate) fn default_max_dif() -> usize {
+    2
+}
+
+pub(crate) fn default_scorer<L: Language>() -> Box<dyn Scorer> {
+    Box::new(ErrorModel::for_language::<L>())
 }
 
 impl<L: Language> SpellChecker<L> {
     pub fn new() -> Result<Self, <Json as ModelFileTrait>::Error> {
         let file = Json::new(format!("{}.json", L::name()));
 
-        file.load_model::<Self>()
+        Self::new_with_file(file)
     }
 
+    /// Loads the checker from a dictionary `file`, like the `{language}.json` one of `new()`.
+    ///
+    /// Returns an error if the dictionary is of another version than the one of the crate.
     pub fn new_with_file<F: filess::ModelFileTrait>(file: F) -> Result<Self, F::Error> {
-        file.load_model::<Self>()
+        let checker = file.load_model::<Self>();
+        // A dictionary of another version may not even load, its version is read alone to say so
+        let version = match &checker {
+            Ok(checker) => checker.dict_meta.version,
+            Err(_) => file
+                .load_model::<VersionProbe>()
+                .map_or(DICT_VERSION, |probe| probe.dict_meta.version),
+        };
+        if version != DICT_VERSION {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("unsupported dictionary version {version}, expected {DICT_VERSION}"),
+            )
+            .into());
+        }
+        checker
     }
 
-    pub fn check(&self, word: &str) -> bool {   // FIXME: Branching may add overhead, compiling this for each language with their checkers would be best
-        if let Some(checker) = &self.ascii_checker {
-            return checker.check(word)
+    /// Loads the checker from a binary dictionary `file` saved with `save_binary()`.
+    ///
+    /// Words are borrowed from the memory mapped file instead of being read and parsed, see [`crate::load_binary_dict()`].
+    /// Returns an error if the dictionary is of another language.
+    pub fn new_binary(file: impl AsRef<Path>) -> io::Result<Self> {
+        let (dict_meta, groups) = load_groups(file)?;
+        if dict_meta.language_short != L::name() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("dictionary is for {}, not {}", dict_meta.language_short, L::name()),
+            ));
         }
-        if let Some(checker) = &self.norm_checker {
-            return checker.check(word)
+        Ok(Self {
+            dict_meta,
+            ascii_checker: Some(ascii::SpellChecker::new(groups)),
+            norm_checker: None,
+            utf8_checker: None,
+            trie_checker: None,
+            affix_checker: None,
+            forbidden: HashMap::new(),
+            language: PhantomData,
+            max_dif: default_max_dif(),
+            scorer: default_scorer::<L>(),
+            compounds: L::compounds(),
+        })
+    }
+
+    /// Saves the ascii checker into a binary dictionary `file`, to be loaded with `new_binary()`.
+    ///
+    /// Only the ascii checker can be saved, the others still need the json dictionary.
+    /// Returns an error if there is no ascii checker, or if there are other checkers or forbidden words that would be lost.
+    pub fn save_binary(&self, file: impl AsRef<Path>) -> io::Result<()> {
+        let Some(checker) = &self.ascii_checker else {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "no ascii checker to save"));
+        };
+        let others = self.norm_checker.is_some()
+            || self.utf8_checker.is_some()
+            || self.trie_checker.is_some()
+            || self.affix_checker.is_some();
+        if others || !self.forbidden.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "only the ascii checker can be saved as binary, save the json dictionary instead",
+            ));
         }
-        if let Some(checker) = &self.utf8_checker {
-            return checker.check(word)
+        let groups = checker.groups();
+        let dict_meta = DictMetadata {
+            words_amount: groups.iter().map(|group| group.count).sum(),
+            included_encodings: vec!["ascii".to_owned()],
+            ..self.dict_meta.clone()
+        };
+        save_groups(file, &dict_meta, groups)
+    }
+
+    /// Checks if the `word` is in any of the sub-checkers matching its encoding.
+    ///
+    /// Ascii words take the fast path through the ascii and trie checkers first, then the normalized and utf-8 ones, which can have ascii words too.
+    /// Other words skip straight to the normalized checker, if they have an ascii form, and to the utf-8 one.
+    /// The affix checker is tried last for any word.
+    /// If `compounds` are on, words made of several dictionary words are accepted too, see `split_compound()`.
+    /// Forbidden words are never accepted.
+    pub fn check(&self, word: &str) -> bool {
+        self.check_word(word) || self.split_compound(word).is_some()
+    }
+
+    /// Forbids the `word`, so it's never correct nor suggested, even if it's in the dictionary.
+    ///
+    /// The `replacement` is suggested first instead of it.
+    pub fn forbid(&mut self, word: &str, replacement: Option<&str>) -> &mut Self {
+        self.forbidden.insert(word.to_lowercase(), replacement.map(str::to_owned));
+        self
+    }
+
+    /// Checks if the `word` is forbidden, see `forbid()`.
+    pub fn is_forbidden(&self, word: &str) -> bool {
+        !self.forbidden.is_empty() && self.forbidden.contains_key(&word.to_lowercase())
+    }
+
+    /// Gets the preferred replacement of a forbidden `word`, if it has one.
+    pub fn replacement(&self, word: &str) -> Option<&str> {
+        self.forbidden.get(&word.to_lowercase())?.as_deref()
+    }
+
+    /// Splits the `word` into the lowercase dictionary words it's made of, linking morphemes left out.
+    ///
+    /// Returns `None` if the `word` isn't made of several dictionary words, if it's forbidden, or if `compounds` are off.
+    pub fn split_compound(&self, word: &str) -> Option<Vec<String>> {
+        let rules = self.compounds?;
+        if self.is_forbidden(word) {
+            return None;
         }
+        let word = word.to_lowercase();
+        let parts = rules.split(&word, |part| self.check_word(part))?;
+        Some(parts.into_iter().map(str::to_owned).collect())
+    }
+
+    /// Checks if the `word` is in any of the sub-checkers and isn't forbidden, without splitting compounds.
+    fn check_word(&self, word: &str) -> bool {
+        !self.is_forbidden(word) && self.checkers_for(word).any(|checker| checker.check(word))
+    }
+
+    pub fn batch_check<'a>(&self, words: &'a [&str]) -> Vec<(&'a str, bool)> {
+        words.iter().map(|&word| (word, self.check(word))).collect()
+    }
+
+    pub fn batch_par_check<'a>(&self, words: &'a [&str]) -> Vec<(&'a str, bool)> {
+        words
+            .par_iter()
+            .map(|&word| (word, self.check(word)))
+            .collect()
+    }
+
+    /// Finds the misspelled words of running `text`, split into words by the tokenizer rules of the language.
+    ///
+    /// Hyphenated words are correct if the whole word or each of its parts is, otherwise each misspelled part is returned on its own.
+    /// Words ending with a clitic of the language, like `dog's`, are correct if the word before it is.
+    /// Misspellings come in the order of the text, without suggestions.
+    pub fn check_text<'t>(&self, text: &'t str) -> Vec<Misspelling<'t, '_>> {
+        self.text_checker().check_text(text)
+    }
+
+    /// Same as `check_text()`, with the first `take_first_x` suggestions for each misspelling, see `suggest()`.
+    ///
+    /// Suggestions for a word with a clitic keep it, like `dog's` for `dpg's`.
+    pub fn suggest_text<'t>(&self, text: &'t str, take_first_x: usize) -> Vec<Misspelling<'t, '_>> {
+        self.text_checker().suggest_text(text, take_first_x)
+    }
+
+    /// Finds the misspelled parts of the identifiers of source `code`, like the `Respnse` of `parseHttpRespnse`, see `tokenize_code()`.
+    ///
+    /// Misspellings come in the order of the code, without suggestions.
+    pub fn check_code<'t>(&self, code: &'t str) -> Vec<Misspelling<'t, '_>> {
+        self.text_checker().check_code(code)
+    }
+
+    /// Same as `check_code()`, with the first `take_first_x` suggestions for each misspelling, see `suggest()`.
+    ///
+    /// Suggestions take the casing of the misspelled part, like `Response` for `Respnse`,
+    /// so replacing the span of the misspelling with one fixes the identifier.
+    pub fn suggest_code<'t>(&self, code: &'t str, take_first_x: usize) -> Vec<Misspelling<'t, '_>> {
+        self.text_checker().suggest_code(code, take_first_x)
+    }
+
+    /// Same as `check_text()`, over the given byte `ranges` of the `text` only, like the comments of source code.
+    ///
+    /// Identifiers mentioned in them, like `snake_case`, are correct if each of their parts is, see `check_code()`.
+    /// Offsets of misspellings are in the whole `text`.
+    pub fn check_ranges<'t>(&self, text: &'t str, ranges: &[Range<usize>]) -> Vec<Misspelling<'t, '_>> {
+        self.text_checker().check_ranges(text, ranges)
+    }
+
+    /// Same as `check_ranges()`, with the first `take_first_x` suggestions for each misspelling, in its casing.
+    pub fn suggest_ranges<'t>(
+        &self,
+        text: &'t str,
+        ranges: &[Range<usize>],
+        take_first_x: usize,
+    ) -> Vec<Misspelling<'t, '_>> {
+        self.text_checker().suggest_ranges(text, ranges, take_first_x)
+    }
+
+    /// Checks the prose of a `file`, with its format picked by its extension.
+    ///
+    /// Only comments and string literals of source code are checked, see `SourceLanguage::extract()`,
+    /// and only prose of markup, see `Markup::extract()`.
+    /// Each misspelling comes with its line, column and first `take_first_x` suggestions, `0` taking all of them.
+    pub fn check_file(&self, file: impl AsRef<Path>, take_first_x: usize) -> io::Result<Vec<Diagnostic<'_>>> {
+        self.text_checker().check_file(file, take_first_x)
+    }
+
+    /// Checks text with the tokenizer rules of the language.
+    fn text_checker(&self) -> TextChecker<'_, Self> {
+        TextChecker::new(self, L::tokenizer())
+    }
+
+    /// Sub-checkers that can have the `word`, picked by its encoding.
+    ///
+    /// Ascii words can be in any of them, the normalized checker finds their accented forms.
+    /// Other words are only looked up in the normalized checker if they have an ascii form, and in the utf-8 one.
+    fn checkers_for(&self, word: &str) -> impl Iterator<Item = &dyn SpellCheckerTrait> {
+        let is_ascii = word.is_ascii();
+        let has_ascii_form = is_ascii || normalize(word).is_some();
+        [
+            self.ascii_checker.as_ref().filter(|_| is_ascii).map(|c| c as &dyn SpellCheckerTrait),
+            self.trie_checker.as_ref().filter(|_| is_ascii).map(|c| c as &dyn SpellCheckerTrait),
+            self.norm_checker.as_ref().filter(|_| has_ascii_form).map(|c| c as &dyn SpellCheckerTrait),
+            self.utf8_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+            self.affix_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+        ]
+        .into_iter()
+        .flatten()
+    }
+
+    /// Finds up to `limit` words starting with `prefix` in all checkers, in lexicographic order.
+    ///
+    /// A `limit` of `0` returns all of them. Forbidden words are left out.
+    pub fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        // Each checker finds enough words to still have `limit` once forbidden ones are left out
+        let checker_limit = if limit == 0 { 0 } else { limit + self.forbidden.len() };
+        let mut words: Vec<&str> = [
+            self.ascii_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+            self.norm_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+            self.utf8_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+            self.trie_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+            self.affix_checker.as_ref().map(|c| c as &dyn SpellCheckerTrait),
+        ]
+        .into_iter()
+        .flatten()
+        .flat_map(|checker| checker.complete(prefix, checker_limit))
+        .filter(|word| !self.is_forbidden(word))
+        .collect();
+
+        words.sort_unstable();
+        words.dedup();
+        if limit != 0 {
+            words.truncate(limit);
+        }
+        words
+    }
+
+    /// Finds all words that are at most `max_dif` away from the `word` in the sub-checkers matching its encoding.
+    ///
+    /// Suggestions of each sub-checker are kept in their order, a word found by several of them is only suggested once.
+    /// If `compounds` are on, compounds with a single misspelled part are suggested after them, with that part corrected.
+    /// Only the `MAX_UNKNOWN_PARTS` shortest parts that could be misspelled are corrected, so long compounds stay fast.
+    /// Forbidden words are left out.
+    pub fn suggest_for_word(&self, word: &str) -> Vec<Suggestion<'_>> {
+        let word = word.to_lowercase();
+        let mut seen = HashSet::new();
+        let mut result: Vec<Suggestion> = self
+            .checkers_for(&word)
+            .flat_map(|checker| checker.suggest_for_word(&word, self.max_dif))
+            .filter(|suggestion| !self.is_forbidden(&suggestion.word))
+            .filter(|suggestion| seen.insert(suggestion.word.clone()))
+            .collect();
+
+        let Some(rules) = self.compounds else {
+            return result;
+        };
+        let chars: Vec<char> = word.chars().collect();
+        let mut ranges = rules.unknown_parts(&word, |part| self.check_word(part));
+        ranges.sort_by_key(|range| range.len());
+        ranges.truncate(MAX_UNKNOWN_PARTS);
+        for range in ranges {
+            let part = &word[range.clone()];
+            for suggestion in self
+                .checkers_for(part)
+                .flat_map(|checker| checker.suggest_for_word(part, self.max_dif))
+            {
+                let compound = format!(
+                    "{}{}{}",
+                    &word[..range.start],
+                    suggestion.word,
+                    &word[range.end..]
+                );
+                if self.is_forbidden(&compound) || !seen.insert(compound.clone().into()) {
+                    continue;
+                }
+                let candidate: Vec<char> = compound.chars().collect();
+                let edits = edit_script(&chars, &candidate, MatchAlgorithm::Osa);
+                result.push(Suggestion {
+                    word: compound.into(),
+                    distance: edits.len(),
+                    edits,
+                    ..suggestion
+                });
+            }
+        }
+        result
+    }
+
+    /// Suggests words for the `word`.
+    ///
+    /// If the `word` is found in the dataset, or is an inflected form the affix checker accepts, returns a vector with the given `word`.
+    /// Compounds are returned as is too, with the id and frequency of their last part.
+    /// Otherwise suggestions from `suggest_for_word()` are sorted by the `scorer`, and the first `take_first_x` are taken, `0` taking all of them.
+    ///
+    /// Forbidden words are never returned, their preferred replacement comes first instead,
+    /// with an id of length `0` if it isn't in the dataset.
+    pub fn suggest(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'_>> {
+        let word = word.to_lowercase();
+        let forbidden = self.forbidden.get(&word);
+        if forbidden.is_none() {
+            if let Some(exact) = self.exact(&word) {
+                return vec![exact];
+            }
+            if let Some(parts) = self.split_compound(&word)
+                && let Some(last) = parts.last().and_then(|last| self.exact(last))
+            {
+                return vec![Suggestion {
+                    word: word.into(),
+                    ..last
+                }];
+            }
+        }
+
+        let mut result = self.suggest_for_word(&word);
+        // Inflected forms have no id, they're only suggested as is
+        if let Some(exact) = result.iter().position(|suggestion| suggestion.distance == 0) {
+            return vec![result.swap_remove(exact)];
+        }
+        if result.len() > 1 {
+            let mut scored: Vec<(f64, Suggestion)> = result
+                .into_par_iter()
+                .map(|suggestion| (self.scorer.score(word.as_bytes(), &suggestion), suggestion))
+                .collect();
+            scored.par_sort_by(|(score1, _), (score2, _)| score1.total_cmp(score2));
+            result = scored.into_iter().map(|(_, suggestion)| suggestion).collect();
+        }
+        if let Some(Some(replacement)) = forbidden {
+            result.retain(|suggestion| suggestion.word != replacement.as_str());
+            result.insert(0, self.replacement_suggestion(&word, replacement));
+        }
+
+        if take_first_x != 0 {
+            result.truncate(take_first_x);
+        }
+        result
+    }
+
+    /// Suggests the preferred `replacement` of a forbidden `word`.
+    fn replacement_suggestion<'a>(&'a self, word: &str, replacement: &'a str) -> Suggestion<'a> {
+        let chars: Vec<char> = word.chars().collect();
+        let candidate: Vec<char> = replacement.chars().collect();
+        let edits = edit_script(&chars, &candidate, MatchAlgorithm::Osa);
+        let found = self.exact(replacement).unwrap_or(Suggestion {
+            word: replacement.into(),
+            id: WordId { len: 0, offset: 0 },
+            distance: 0,
+            frequency: 0,
+            edits: vec![],
+        });
+        Suggestion {
+            distance: edits.len(),
+            edits,
+            ..found
+        }
+    }
+
+    /// Finds the lowercase `word` as is in the sub-checkers.
+    fn exact(&self, word: &str) -> Option<Suggestion<'_>> {
+        self.checkers_for(word).find_map(|checker| {
+            let id = checker.find(word)?;
+            Some(Suggestion::exact(checker.get_unchecked(id), id, checker.frequency(id)))
+        })
+    }
+
+    /// Suggests words for each `word` in the given `words`, see `suggest()`.
+    pub fn batch_suggest<'a>(
+        &self,
+        words: &'a [&str],
+        take_first_x: usize,
+    ) -> Vec<(&'a str, Vec<Suggestion<'_>>)> {
+        self.batch_suggest_iter(words, take_first_x).collect()
+    }
+
+    /// Calls the `callback` with each `word` in the given `words` and its suggestions, see `suggest()`.
+    pub fn batch_suggest_with<F>(&self, words: &[&str], take_first_x: usize, mut callback: F)
+    where
+        F: FnMut(&str, Vec<Suggestion<'_>>),
+    {
+        words.iter().for_each(move |word| {
+            let suggestions = self.suggest(word, take_first_x);
+            callback(word, suggestions)
+        });
+    }
+
+    /// Iterates over each `word` in the given `words` and its suggestions, see `suggest()`.
+    pub fn batch_suggest_iter<'a>(
+        &self,
+        words: &'a [&str],
+        take_first_x: usize,
+    ) -> impl Iterator<Item = (&'a str, Vec<Suggestion<'_>>)> {
+        words
+            .iter()
+            .map(move |&word| (word, self.suggest(word, take_first_x)))
+    }
+
+    /// Same as `batch_suggest()`, but suggests for all words in parallel.
+    pub fn batch_par_suggest<'a>(
+        &self,
+        words: &'a [&str],
+        take_first_x: usize,
+    ) -> Vec<(&'a str, Vec<Suggestion<'_>>)> {
+        self.batch_par_suggest_iter(words, take_first_x).collect()
+    }
+
+    /// Same as `batch_suggest_with()`, but suggests for all words in parallel.
+    pub fn batch_par_suggest_with<F>(&self, words: &[&str], take_first_x: usize, callback: F)
+    where
+        F: FnMut(&str, Vec<Suggestion<'_>>) + Send + Sync + Clone,
+    {
+        words.par_iter().for_each_with(callback, move |cb, word| {
+            let suggestions = self.suggest(word, take_first_x);
+            cb(word, suggestions)
+        });
+    }
 
-        false
+    /// Same as `batch_suggest_iter()`, but a parallel iterator.
+    pub fn batch_par_suggest_iter<'a>(
+        &self,
+        words: &'a [&str],
+        take_first_x: usize,
+    ) -> impl ParallelIterator<Item = (&'a str, Vec<Suggestion<'_>>)> {
+        words
+            .par_iter()
+            .map(move |&word| (word, self.suggest(word, take_first_x)))
     }
 }
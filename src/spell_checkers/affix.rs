@@ -0,0 +1,284 @@
+use std::collections::HashSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MatchAlgorithm, Suggestion, WordId, edit_script,
+    hunspell::{AffixKind, AffixRule, Affixes, Flag, Hunspell},
+    spell_checkers::{
+        SpellCheckerTrait,
+        utf8::{self, words_to_char_groups},
+    },
+};
+
+/// Spell checker over the stems and affix rules of a Hunspell dictionary, without expanding them.
+///
+/// Inflected words are checked by stripping their affixes and looking up the stem, so the dictionary stays
+/// as small as the `.dic` file, while accepting the same words as [`Hunspell::expand()`].
+/// Suggestions are inflected forms of stems close to the word or to the word without its affixes.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpellChecker {
+    /// Stems grouped by their amount of chars.
+    stems: utf8::SpellChecker,
+    /// Flags of each stem, in the same order as the groups of stems.
+    flags: Vec<Vec<Vec<Flag>>>,
+    affixes: Affixes,
+}
+
+impl SpellChecker {
+    /// Creates the checker from the stems of the `dict`, `None` if it has none.
+    ///
+    /// Forbidden stems are left out, and flags of stems listed several times are merged.
+    pub fn new(dict: &Hunspell) -> Option<Self> {
+        let affixes = dict.affixes.clone();
+        let stems: Vec<_> = dict
+            .stems
+            .iter()
+            .filter(|stem| !has(&stem.flags, affixes.forbidden))
+            .collect();
+        let groups = words_to_char_groups(stems.iter().map(|stem| stem.word.clone()).collect())?;
+
+        let mut flags: Vec<Vec<Vec<Flag>>> = groups
+            .iter()
+            .map(|group| vec![vec![]; group.count()])
+            .collect();
+        // Whether any entry of the stem is a word on its own
+        let mut bare: Vec<Vec<bool>> = groups
+            .iter()
+            .map(|group| vec![false; group.count()])
+            .collect();
+        for stem in stems {
+            let word = stem.word.to_lowercase();
+            let len = word.chars().count();
+            let Some(index) = len.checked_sub(1).and_then(|gi| groups[gi].find(&word)) else {
+                continue;
+            };
+            let merged = &mut flags[len - 1][index];
+            for flag in &stem.flags {
+                if !merged.contains(flag) {
+                    merged.push(*flag);
+                }
+            }
+            bare[len - 1][index] |= !has(&stem.flags, affixes.need_affix)
+                && !has(&stem.flags, affixes.only_in_compound);
+        }
+        for (group, bare) in flags.iter_mut().zip(bare) {
+            for (flags, bare) in group.iter_mut().zip(bare) {
+                if bare {
+                    flags.retain(|flag| {
+                        Some(*flag) != affixes.need_affix && Some(*flag) != affixes.only_in_compound
+                    });
+                }
+            }
+        }
+
+        Some(Self {
+            stems: utf8::SpellChecker::new(groups),
+            flags,
+            affixes,
+        })
+    }
+
+    /// Builds a `BkTree` over all stems and uses it to find the stems suggestions are made from.
+    pub fn build_bk_tree(&mut self) -> &mut Self {
+        self.stems.build_bk_tree();
+        self
+    }
+
+    /// Groups of stems the checker was built over.
+    pub(crate) fn groups_mut(&mut self) -> &mut [utf8::LenGroup] {
+        self.stems.groups_mut()
+    }
+
+    fn flags_of(&self, stem: WordId) -> Option<&[Flag]> {
+        let group = self.stems.groups().get(stem.len.checked_sub(1)?)?;
+        let index = group.index_of(stem.offset)?;
+        Some(&self.flags[stem.len - 1][index])
+    }
+
+    /// Checks if the `stem` is in the dataset with flags that `accept` it.
+    fn has_stem(&self, stem: &str, accept: impl Fn(&[Flag]) -> bool) -> bool {
+        self.stems
+            .find(stem)
+            .and_then(|id| self.flags_of(id))
+            .is_some_and(accept)
+    }
+
+    /// All rules of the `kind`, with the flag and cross product of their class.
+    fn rules(&self, kind: AffixKind) -> impl Iterator<Item = (Flag, &AffixRule, bool)> {
+        self.affixes
+            .classes
+            .iter()
+            .filter(move |(_, class)| class.kind == kind)
+            .flat_map(|(flag, class)| {
+                class
+                    .rules
+                    .iter()
+                    .map(move |rule| (*flag, rule, class.cross_product))
+            })
+    }
+
+    /// The `word` itself and the `word` without one or two suffixes, a prefix or both, conditions not checked.
+    fn guess_stems(&self, word: &str) -> Vec<String> {
+        let remove = |kind: AffixKind, word: &str| -> Vec<String> {
+            self.rules(kind)
+                .filter(|(_, rule, _)| !rule.add.is_empty())
+                .filter_map(|(_, rule, _)| rule.remove(word))
+                .filter(|stem| !stem.is_empty())
+                .collect()
+        };
+
+        let mut guesses = vec![word.to_owned()];
+        guesses.extend(remove(AffixKind::Suffix, word));
+        // A suffix can have another one after it
+        let suffixed: Vec<String> = guesses[1..]
+            .iter()
+            .flat_map(|guess| remove(AffixKind::Suffix, guess))
+            .collect();
+        guesses.extend(suffixed);
+        let prefixed: Vec<String> = guesses
+            .iter()
+            .flat_map(|guess| remove(AffixKind::Prefix, guess))
+            .collect();
+        guesses.extend(prefixed);
+        guesses.sort_unstable();
+        guesses.dedup();
+        guesses
+    }
+}
+
+impl SpellCheckerTrait for SpellChecker {
+    fn get(&self, word: WordId) -> Option<&str> {
+        self.stems.get(word)
+    }
+
+    fn get_unchecked(&self, word: WordId) -> &str {
+        self.stems.get_unchecked(word)
+    }
+
+    /// Checks if the `word` is a stem on its own, or a stem with affixes its flags allow.
+    ///
+    /// Affixes are stripped the same way [`Affixes::expand()`] adds them: a suffix, a suffix with another one after it,
+    /// a prefix, or a prefix and a suffix if both allow their cross product.
+    fn check(&self, word: &str) -> bool {
+        let word = word.to_lowercase();
+        let need_affix = self.affixes.need_affix;
+        if self.find(&word).is_some() {
+            return true;
+        }
+
+        for (flag, suffix, _) in self.rules(AffixKind::Suffix) {
+            let Some(rest) = suffix.unapply(&word) else {
+                continue;
+            };
+            if has(&suffix.continuation, need_affix) {
+                continue;
+            }
+            if self.has_stem(&rest, |flags| flags.contains(&flag)) {
+                return true;
+            }
+            for (inner_flag, inner, _) in self.rules(AffixKind::Suffix) {
+                if inner.continuation.contains(&flag)
+                    && let Some(stem) = inner.unapply(&rest)
+                    && self.has_stem(&stem, |flags| flags.contains(&inner_flag))
+                {
+                    return true;
+                }
+            }
+        }
+
+        for (flag, prefix, cross_product) in self.rules(AffixKind::Prefix) {
+            let Some(rest) = prefix.unapply(&word) else {
+                continue;
+            };
+            if has(&prefix.continuation, need_affix) {
+                continue;
+            }
+            if self.has_stem(&rest, |flags| flags.contains(&flag)) {
+                return true;
+            }
+            if !cross_product {
+                continue;
+            }
+            for (suffix_flag, suffix, suffix_cross_product) in self.rules(AffixKind::Suffix) {
+                if suffix_cross_product
+                    && let Some(stem) = suffix.unapply(&rest)
+                    && prefix.condition.matches_start(&stem)
+                    && self.has_stem(&stem, |flags| {
+                        flags.contains(&suffix_flag)
+                            && (flags.contains(&flag) || suffix.continuation.contains(&flag))
+                    })
+                {
+                    return true;
+                }
+            }
+        }
+        false
+    }
+
+    /// Finds the `WordId` of a stem that is a word on its own, inflected words have no id of their own.
+    fn find(&self, word: &str) -> Option<WordId> {
+        let id = self.stems.find(word)?;
+        let flags = self.flags_of(id)?;
+        (!has(flags, self.affixes.need_affix) && !has(flags, self.affixes.only_in_compound))
+            .then_some(id)
+    }
+
+    fn frequency(&self, word: WordId) -> u32 {
+        self.stems.frequency(word)
+    }
+
+    /// Finds all forms of stems that are at most `max_dif` `deletions`, `insertions`, `substitutions` or transpositions of chars away from the `word`.
+    ///
+    /// Stems are searched close to the `word` and to the `word` without its affixes, then expanded and their forms matched against the `word`.
+    /// Generated forms are owned by the suggestion, which gets the `WordId` and frequency of their stem.
+    fn suggest_for_word(&self, word: &str, max_dif: usize) -> Vec<Suggestion<'_>> {
+        let lowercase = word.to_lowercase();
+        let word: Vec<char> = lowercase.chars().collect();
+        if word.is_empty() {
+            return vec![];
+        }
+
+        let mut stems = HashSet::new();
+        let mut forms = HashSet::new();
+        let mut result = vec![];
+        for guess in self.guess_stems(&lowercase) {
+            for stem in self.stems.suggest_for_word(&guess, max_dif) {
+                if !stems.insert(stem.id) {
+                    continue;
+                }
+                let Some(flags) = self.flags_of(stem.id) else {
+                    continue;
+                };
+                for form in self.affixes.expand(&stem.word, flags) {
+                    let candidate: Vec<char> = form.chars().collect();
+                    if MatchAlgorithm::Osa
+                        .distance(&word, &candidate, max_dif)
+                        .is_none()
+                        || !forms.insert(form.clone())
+                    {
+                        continue;
+                    }
+                    let edits = edit_script(&word, &candidate, MatchAlgorithm::Osa);
+                    result.push(Suggestion {
+                        word: form.into(),
+                        id: stem.id,
+                        distance: edits.len(),
+                        frequency: stem.frequency,
+                        edits,
+                    });
+                }
+            }
+        }
+        result
+    }
+
+    /// Finds up to `limit` stems starting with `prefix`, inflected forms aren't completed.
+    fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        self.stems.complete(prefix, limit)
+    }
+}
+
+fn has(flags: &[Flag], flag: Option<Flag>) -> bool {
+    flag.is_some_and(|flag| flags.contains(&flag))
+}
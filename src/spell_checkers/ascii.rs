@@ -1,25 +1,51 @@
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{WordId, spell_checkers::{SpellCheckerTrait, simple_len_group::WordGroup}};
+use crate::{BkTree, indexes, MatchAlgorithm, Suggestion, SuggestionIndex, WordBlob, WordId, edit_script, spell_checkers::{SpellCheckerTrait, simple_len_group::WordGroup}};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpellChecker {
     groups: Vec<WordGroup>,
+    /// Index used for suggestions, saved together with the groups.
+    #[serde(default)]
+    index: SuggestionIndex,
 }
 
 impl SpellChecker {
     pub fn new(word_groups: Vec<WordGroup>) -> Self {
         Self {
             groups: word_groups,
+            index: SuggestionIndex::default(),
             // added_words: vec![],
             // added_words_treshhold: 20,
         }
     }
+
+    /// Builds a `BkTree` over all words and uses it for suggestions.
+    pub fn build_bk_tree(&mut self) -> &mut Self {
+        self.index = SuggestionIndex::BkTree(BkTree::new(&self.groups));
+        self
+    }
+
+    /// Groups of words the checker was built over, by length.
+    pub(crate) fn groups(&self) -> &[WordGroup] {
+        &self.groups
+    }
+
+    /// Mutable groups of words, to set their frequencies.
+    pub(crate) fn groups_mut(&mut self) -> &mut [WordGroup] {
+        &mut self.groups
+    }
+
+    /// Gets the `WordGroup` of words with `len` bytes, groups are stored starting from length `1`.
+    fn group_of_len(&self, len: usize) -> Option<&WordGroup> {
+        self.groups.get(len.checked_sub(1)?)
+    }
 }
 
 impl SpellCheckerTrait for SpellChecker {
     fn get(&self, word: WordId) -> Option<&str> {
-        let wg = self.groups.get(word.len)?;
+        let wg = self.group_of_len(word.len)?;
         if word.offset >= wg.blob.len() {
             None
         } else {
@@ -30,16 +56,76 @@ impl SpellCheckerTrait for SpellChecker {
     fn get_unchecked(&self, word: WordId) -> &str {
         let lg = self
             .groups
-            .get(word.len)
-            .expect(&format!("LenGroup of len {} should exist", word.len));
+            .get(word.len - 1)
+            .unwrap_or_else(|| panic!("LenGroup of len {} should exist", word.len));
         &lg.blob[word.offset..word.offset + word.len]
     }
 
     fn check(&self, word: &str) -> bool {
-        let group = self.groups.get(word.len());
+        let group = self.group_of_len(word.len());
         match group {
             Some(wg) => wg.check(word),
             None => false,
         }
     }
+
+    fn find(&self, word: &str) -> Option<WordId> {
+        let group = self.group_of_len(word.len())?;
+        Some(WordId {
+            len: group.len,
+            offset: group.find(word)?.0,
+        })
+    }
+
+    fn frequency(&self, word: WordId) -> u32 {
+        self.group_of_len(word.len).map_or(0, |wg| wg.frequency(word.offset))
+    }
+
+    /// Only the groups with at most `max_dif` bytes more or less are searched, or only the candidates of the `BkTree` if it was built.
+    /// Suggestions are returned in the same order as the groups.
+    fn suggest_for_word(&self, word: &str, max_dif: usize) -> Vec<Suggestion<'_>> {
+        let word = word.as_bytes();
+        if word.is_empty() {
+            return vec![];
+        }
+
+        let matches = |gi: usize, wi: usize| -> Option<Suggestion<'_>> {
+            let group = &self.groups[gi];
+            let candidate = group.word(wi);
+            MatchAlgorithm::Osa.distance(word, candidate, max_dif)?;
+            let edits = edit_script(word, candidate, MatchAlgorithm::Osa);
+            Some(Suggestion {
+                word: group.blob[wi * group.len..(wi + 1) * group.len].into(),
+                id: WordId {
+                    len: group.len,
+                    offset: wi * group.len,
+                },
+                distance: edits.len(),
+                frequency: group.frequency(wi * group.len),
+                edits,
+            })
+        };
+
+        if let Some(candidates) = self.index.candidates(&self.groups, word, max_dif) {
+            return candidates
+                .into_par_iter()
+                .filter_map(|(gi, wi)| matches(gi, wi))
+                .collect();
+        }
+
+        let min_len = word.len().saturating_sub(max_dif).max(1);
+        let max_len = (word.len() + max_dif).min(self.groups.len());
+        (min_len..=max_len)
+            .into_par_iter()
+            .flat_map(|len| {
+                (0..self.groups[len - 1].count)
+                    .into_par_iter()
+                    .filter_map(move |wi| matches(len - 1, wi))
+            })
+            .collect()
+    }
+
+    fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        indexes::complete_words(&self.groups, prefix, limit)
+    }
 }
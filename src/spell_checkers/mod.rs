@@ -1,16 +1,20 @@
+pub mod affix;
 pub mod ascii;
 pub mod normalized;
 pub mod simple_len_group;
+pub mod trie;
 pub mod utf8;
 
 pub use simple_len_group::words_to_groups;
 
-use crate::WordId;
+use crate::{FrequencyScorer, Scorer, Suggestion, WordId};
 
 pub enum SpellCheckerTypes {
     Ascii(ascii::SpellChecker),
     Normalized(normalized::SpellChecker),
     Utf8(utf8::SpellChecker),
+    Trie(trie::SpellChecker),
+    Affix(affix::SpellChecker),
 }
 
 pub trait SpellCheckerTrait {
@@ -18,4 +22,36 @@ pub trait SpellCheckerTrait {
     fn get_unchecked(&self, word: WordId) -> &str;
     
     fn check(&self, word: &str) -> bool;
+
+    /// Finds the `WordId` of a word.
+    fn find(&self, word: &str) -> Option<WordId>;
+
+    /// Gets the frequency of a word, `0` if the dataset has none.
+    fn frequency(&self, word: WordId) -> u32;
+
+    /// Finds all words that are at most `max_dif` `deletions`, `insertions`, `substitutions` or transpositions away from the `word`.
+    fn suggest_for_word(&self, word: &str, max_dif: usize) -> Vec<Suggestion<'_>>;
+
+    /// Suggests words for the `word`, only the `word` itself if it's in the dataset.
+    ///
+    /// Suggestions are ordered by the [`FrequencyScorer`], `take_first_x` of `0` returns all of them.
+    fn suggest(&self, word: &str, max_dif: usize, take_first_x: usize) -> Vec<Suggestion<'_>> {
+        if let Some(id) = self.find(word) {
+            return vec![Suggestion::exact(self.get_unchecked(id), id, self.frequency(id))];
+        }
+
+        let word = word.to_lowercase();
+        let mut result = self.suggest_for_word(&word, max_dif);
+        result.sort_by(|s1, s2| {
+            let score1 = FrequencyScorer.score(word.as_bytes(), s1);
+            score1.total_cmp(&FrequencyScorer.score(word.as_bytes(), s2))
+        });
+        if take_first_x != 0 {
+            result.truncate(take_first_x);
+        }
+        result
+    }
+
+    /// Finds up to `limit` words starting with `prefix`, in lexicographic order, `0` meaning all of them.
+    fn complete(&self, prefix: &str, limit: usize) -> Vec<&str>;
 }
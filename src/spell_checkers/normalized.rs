@@ -1,51 +1,330 @@
+use std::collections::HashMap;
+
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
+use unicode_normalization::{UnicodeNormalization, char::is_combining_mark};
 
-use crate::spell_checkers::SpellCheckerTrait;
+use crate::{
+    BkTree, MatchAlgorithm, Suggestion, SuggestionIndex, WordBlob, WordId, edit_script, indexes,
+    spell_checkers::SpellCheckerTrait,
+};
 
-/// A group that stores the ascii words blob, whose indexes correspond to utf8 blob of words of given len.
+/// Strips diacritics from the `word`, returning its ascii form.
 ///
-/// This struct is used to store the ascii words blob and the corresponding utf8 blob of words of the same length.
-/// The ascii words blob is a string of all the ascii words in the dataset, concatenated together without any delimiters.
-/// The utf8 blob of words is a string of all the utf8 words in the dataset, concatenated together without any delimiters.
-/// The len field stores the length of the words in utf8 blob, whereas ascii len should be stored elsewhere.
-pub struct AsciiUtf8WordGroup {
-    // pub blob_ascii: String,             // Ascii words of this length
-    // /// Maps ascii words to utf8 by index.
-    // ascii_to_utf8: Vec<usize>,
-    // pub blob_utf8: String,
-    pub len: usize,
+/// The word is decomposed (NFD) and combining marks are dropped, a few Latin letters without a decomposition are spelled out (`ß` -> `ss`).
+/// Returns `None` if some char still has no ascii form, like Cyrillic or Greek letters.
+pub fn normalize(word: &str) -> Option<String> {
+    let mut ascii = String::with_capacity(word.len());
+    for ch in word.nfd().filter(|ch| !is_combining_mark(*ch)) {
+        match ch {
+            _ if ch.is_ascii() => ascii.push(ch),
+            'ß' => ascii.push_str("ss"),
+            'æ' => ascii.push_str("ae"),
+            'œ' => ascii.push_str("oe"),
+            'ø' => ascii.push('o'),
+            'ł' => ascii.push('l'),
+            'đ' | 'ð' => ascii.push('d'),
+            'þ' => ascii.push_str("th"),
+            'ı' => ascii.push('i'),
+            _ => return None,
+        }
+    }
+    Some(ascii)
 }
 
+/// A group that stores the ascii words blob, whose indexes correspond to utf8 blob of words.
+///
+/// The ascii words blob is a string of the ascii forms of all the words in the dataset with the same ascii length, concatenated together without any delimiters.
+/// Different utf8 words can have the same ascii form (`cafe` and `café`), in which case it's stored once for each of them.
+/// The utf8 blob of words is a string of all the utf8 words, in the same order as their ascii forms, concatenated together without any delimiters.
+/// The len field stores the length of the words in the ascii blob, utf8 words can have any length.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct WordGroup {
-    // pub ascii_utf8_word_groups: Vec<AsciiUtf8WordGroup>,
     pub blob_ascii: String,
     /// Maps ascii words to utf8 by index.
-    /// Index in this list by ascii word index to get corresponding utf8 index.
+    /// Index in this list by ascii word index to get the offset of the corresponding utf8 word in `blob_utf8`.
     ascii_to_utf8: Vec<usize>,
     pub blob_utf8: String,
     pub len: usize,
+    /// Frequency of each utf8 word, empty if the dataset has none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freq: Vec<u32>,
 }
 
 impl WordGroup {
-    
+    pub fn empty(len: usize) -> Self {
+        Self {
+            blob_ascii: String::new(),
+            ascii_to_utf8: vec![],
+            blob_utf8: String::new(),
+            len,
+            freq: vec![],
+        }
+    }
+
+    /// Amount of words in the group.
+    pub fn count(&self) -> usize {
+        self.ascii_to_utf8.len()
+    }
+
+    /// Gets the utf8 word at `index`.
+    pub fn utf8(&self, index: usize) -> Option<&str> {
+        let start = *self.ascii_to_utf8.get(index)?;
+        let end = self
+            .ascii_to_utf8
+            .get(index + 1)
+            .copied()
+            .unwrap_or(self.blob_utf8.len());
+        Some(&self.blob_utf8[start..end])
+    }
+
+    /// Finds the indexes of all utf8 words with the `ascii` form.
+    pub fn find_ascii(&self, ascii: &str) -> std::ops::Range<usize> {
+        if ascii.len() != self.len {
+            return 0..0;
+        }
+        self.prefix_range(ascii.as_bytes())
+    }
+
+    /// Gets the frequency of the word at `index`, `0` if the dataset has none.
+    pub fn frequency(&self, index: usize) -> u32 {
+        self.freq.get(index).copied().unwrap_or(0)
+    }
+
+    /// Sets the frequency of each utf8 word from `frequencies`, words missing from it get `0`.
+    pub fn set_frequencies(&mut self, frequencies: &HashMap<String, u32>) {
+        self.freq = (0..self.count())
+            .map(|i| {
+                self.utf8(i)
+                    .and_then(|w| frequencies.get(w))
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect();
+    }
+}
+
+impl WordBlob for WordGroup {
+    fn blob(&self) -> &[u8] {
+        self.blob_ascii.as_bytes()
+    }
+
+    fn word_len(&self) -> usize {
+        self.len
+    }
+}
+
+/// Groups `words` by the length of their ascii form, see [`normalize`].
+///
+/// Words without an ascii form are skipped.
+pub fn words_to_norm_groups(words: Vec<String>) -> Option<Vec<WordGroup>> {
+    let mut words: Vec<(String, String)> = words
+        .par_iter()
+        .filter_map(|w| {
+            let w = w.to_lowercase();
+            let ascii = normalize(&w)?;
+            (!ascii.is_empty()).then_some((ascii, w))
+        })
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    words.par_sort_unstable_by(|(a1, w1), (a2, w2)| {
+        a1.len().cmp(&a2.len()).then(a1.cmp(a2)).then(w1.cmp(w2))
+    });
+    words.dedup();
+    let biggest_len = words.last().unwrap().0.len();
+
+    let mut groups: Vec<WordGroup> = (1..=biggest_len).map(WordGroup::empty).collect();
+    for (ascii, word) in words {
+        let group = &mut groups[ascii.len() - 1];
+        group.blob_ascii.push_str(&ascii);
+        group.ascii_to_utf8.push(group.blob_utf8.len());
+        group.blob_utf8.push_str(&word);
+    }
+    Some(groups)
 }
 
+/// Spell checker for words with diacritics.
+///
+/// Words are searched by their ascii forms, and matches are mapped back to the accented utf8 words,
+/// so `cafe` finds `café`, and `naive` finds `naïve`.
+///
+/// `WordId`s point into the ascii blobs, `len` is the ascii length.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpellChecker {
     len_groups: Vec<WordGroup>,
+    /// Index used for suggestions, saved together with the groups.
+    #[serde(default)]
+    index: SuggestionIndex,
+}
+
+impl SpellChecker {
+    pub fn new(len_groups: Vec<WordGroup>) -> Self {
+        Self {
+            len_groups,
+            index: SuggestionIndex::default(),
+        }
+    }
+
+    /// Builds a `BkTree` over the ascii forms of all words and uses it for suggestions.
+    pub fn build_bk_tree(&mut self) -> &mut Self {
+        self.index = SuggestionIndex::BkTree(BkTree::new(&self.len_groups));
+        self
+    }
+
+    /// Groups of words the checker was built over.
+    pub(crate) fn groups_mut(&mut self) -> &mut [WordGroup] {
+        &mut self.len_groups
+    }
+
+    /// Finds all utf8 words with the same ascii form as the `word`, ignoring diacritics.
+    pub fn forms(&self, word: &str) -> Vec<&str> {
+        let Some(ascii) = normalize(&word.to_lowercase()) else {
+            return vec![];
+        };
+        let Some(group) = ascii
+            .len()
+            .checked_sub(1)
+            .and_then(|i| self.len_groups.get(i))
+        else {
+            return vec![];
+        };
+        group
+            .find_ascii(&ascii)
+            .filter_map(|i| group.utf8(i))
+            .collect()
+    }
 }
 
 impl SpellCheckerTrait for SpellChecker {
-    fn check(&self, _word: &str) -> bool {
-        false
+    fn check(&self, word: &str) -> bool {
+        self.find(word).is_some()
+    }
+
+    /// Finds the `WordId` of a utf8 word, diacritics have to match.
+    fn find(&self, word: &str) -> Option<WordId> {
+        let word = word.to_lowercase();
+        let ascii = normalize(&word)?;
+        let group = self.len_groups.get(ascii.len().checked_sub(1)?)?;
+        let index = group
+            .find_ascii(&ascii)
+            .find(|i| group.utf8(*i) == Some(word.as_str()))?;
+        Some(WordId {
+            len: group.len,
+            offset: index * group.len,
+        })
+    }
+
+    /// Finds all words whose ascii form is at most `max_dif` `deletions`, `insertions`, `substitutions` or transpositions away from the ascii form of the `word`.
+    ///
+    /// Suggestions are ordered by the distance of the ascii forms, then in the same order as the groups.
+    /// Their `distance` and `edits` are between the lowercase `word` and the utf8 suggestion, in chars,
+    /// so `cafe` suggests `café` with a distance of `1`, even though their ascii forms are the same.
+    fn suggest_for_word(&self, word: &str, max_dif: usize) -> Vec<Suggestion<'_>> {
+        let word = word.to_lowercase();
+        let Some(ascii) = normalize(&word) else {
+            return vec![];
+        };
+        if ascii.is_empty() {
+            return vec![];
+        }
+        let ascii = ascii.as_bytes();
+        let chars: Vec<char> = word.chars().collect();
+
+        let matches = |gi: usize, wi: usize| -> Option<(usize, Suggestion<'_>)> {
+            let group = &self.len_groups[gi];
+            let ascii_dist = MatchAlgorithm::Osa.distance(ascii, group.word(wi), max_dif)?;
+            let utf8 = group.utf8(wi)?;
+            let candidate: Vec<char> = utf8.chars().collect();
+            let edits = edit_script(&chars, &candidate, MatchAlgorithm::Osa);
+            Some((
+                ascii_dist,
+                Suggestion {
+                    word: utf8.into(),
+                    id: WordId {
+                        len: group.len,
+                        offset: wi * group.len,
+                    },
+                    distance: edits.len(),
+                    frequency: group.frequency(wi),
+                    edits,
+                },
+            ))
+        };
+
+        let mut found: Vec<(usize, Suggestion)> =
+            match self.index.candidates(&self.len_groups, ascii, max_dif) {
+                // The tree keeps one word of each ascii form, the others are next to it
+                Some(candidates) => candidates
+                    .into_par_iter()
+                    .flat_map_iter(|(gi, wi)| {
+                        let group = &self.len_groups[gi];
+                        group.prefix_range(group.word(wi)).map(move |wi| (gi, wi))
+                    })
+                    .filter_map(|(gi, wi)| matches(gi, wi))
+                    .collect(),
+                None => {
+                    let min_len = ascii.len().saturating_sub(max_dif).max(1);
+                    let max_len = (ascii.len() + max_dif).min(self.len_groups.len());
+                    (min_len..=max_len)
+                        .into_par_iter()
+                        .flat_map(|len| {
+                            (0..self.len_groups[len - 1].count())
+                                .into_par_iter()
+                                .filter_map(move |wi| matches(len - 1, wi))
+                        })
+                        .collect()
+                }
+            };
+        found.par_sort_by_key(|(ascii_dist, _)| *ascii_dist);
+        found
+            .into_iter()
+            .map(|(_, suggestion)| suggestion)
+            .collect()
+    }
+
+    fn frequency(&self, word: WordId) -> u32 {
+        word.len
+            .checked_sub(1)
+            .and_then(|i| self.len_groups.get(i))
+            .map_or(0, |group| group.frequency(word.offset / group.len))
+    }
+
+    fn get(&self, word: WordId) -> Option<&str> {
+        let group = self.len_groups.get(word.len.checked_sub(1)?)?;
+        group.utf8(word.offset / group.len)
     }
 
-    fn get(&self, _word: crate::WordId) -> Option<&str> {
-        None
+    fn get_unchecked(&self, word: WordId) -> &str {
+        let group = self
+            .len_groups
+            .get(word.len - 1)
+            .unwrap_or_else(|| panic!("WordGroup of len {} should exist", word.len));
+        group
+            .utf8(word.offset / group.len)
+            .unwrap_or_else(|| panic!("Word should exist at offset {}", word.offset))
     }
 
-    fn get_unchecked(&self, _word: crate::WordId) -> &str {
-        ""
+    fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        let Some(ascii) = normalize(&prefix.to_lowercase()) else {
+            return vec![];
+        };
+        let lowercase = prefix.to_lowercase();
+        // Prefixes with diacritics only match words with the same ones
+        let mut found = vec![];
+        for (group, index) in indexes::complete(&self.len_groups, ascii.as_bytes(), 0) {
+            let Some(word) = self.len_groups[group].utf8(index) else {
+                continue;
+            };
+            if ascii == lowercase || word.starts_with(&lowercase) {
+                found.push(word);
+                if found.len() == limit {
+                    break;
+                }
+            }
+        }
+        found
     }
 }
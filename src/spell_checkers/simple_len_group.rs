@@ -1,24 +1,50 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
 use rayon::iter::{IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Serialize};
 
+use crate::{Blob, Frequencies};
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WordGroup {
-    pub blob: String,
+    pub blob: Blob,
     pub len: usize,
     pub count: usize,
+    /// Frequency of each word in the blob, empty if the dataset has none.
+    #[serde(default, skip_serializing_if = "Frequencies::is_empty")]
+    pub freq: Frequencies,
 }
 
 impl WordGroup {
     pub fn empty(len: usize) -> Self {
         Self {
-            blob: String::new(),
+            blob: Blob::default(),
             len,
             count: 0,
+            freq: Frequencies::default(),
         }
     }
 
+    /// Gets the frequency of the word at `offset`, `0` if the dataset has none.
+    pub fn frequency(&self, offset: usize) -> u32 {
+        self.freq.get(offset / self.len).unwrap_or(0)
+    }
+
+    /// Sets the frequency of each word from `frequencies`, words missing from it get `0`.
+    pub fn set_frequencies(&mut self, frequencies: &HashMap<String, u32>) {
+        self.freq = self
+            .blob
+            .as_bytes()
+            .chunks(self.len)
+            .map(|word| {
+                // Words are cut at their length, so each chunk is valid utf-8
+                let word = std::str::from_utf8(word).unwrap();
+                frequencies.get(word).copied().unwrap_or(0)
+            })
+            .collect::<Vec<_>>()
+            .into();
+    }
+
     /// Checks if a word exists in the dataset.
     ///
     /// Returns true if the word exists, false otherwise.
@@ -50,7 +76,9 @@ impl WordGroup {
 }
 
 pub enum BinarySearchWordResult {
+    /// Start and end offsets of the word.
     Found(usize, usize),
+    /// Start and end offsets the word would have if it was inserted, keeping the blob sorted.
     NotFound(usize, usize),
 }
 
@@ -58,10 +86,9 @@ pub fn find_word_in_slice_binary_search(word: &[u8], slice: &[u8]) -> BinarySear
     // Supports both ascii and utf-8 without a problem
     let mut low = 0usize;
     let mut high = slice.len().checked_div(word.len()).unwrap();
-    let mut mid_off = 0;
     while low < high {
         let mid = low + ((high - low) / 2);
-        mid_off = mid * word.len();
+        let mid_off = mid * word.len();
         let candidate = &slice[mid_off..(mid_off + word.len())];
         match word.cmp(candidate) {
             Ordering::Equal => return BinarySearchWordResult::Found(mid_off, mid_off + word.len()),
@@ -69,24 +96,25 @@ pub fn find_word_in_slice_binary_search(word: &[u8], slice: &[u8]) -> BinarySear
             Ordering::Greater => low = mid + 1,
         }
     }
-    BinarySearchWordResult::NotFound(mid_off, mid_off + word.len())
+    BinarySearchWordResult::NotFound(low * word.len(), (low + 1) * word.len())
 }
 
 pub fn words_to_groups(mut words: Vec<String>) -> Option<Vec<WordGroup>> {
-    words = words.par_iter().filter(|w| w.is_empty()).map(|w| w.to_lowercase()).collect();
+    words = words.par_iter().filter(|w| !w.is_empty()).map(|w| w.to_lowercase()).collect();
     if words.is_empty() { return None }
     
     words.sort_unstable_by(|w1, w2| w1.len().cmp(&w2.len()).then(w1.cmp(w2)));  // TODO: Check if unstable preserves needed order
+    words.dedup();
     let biggest_len = words.last().unwrap().len();
     
     let mut groups: Vec<WordGroup> = Vec::with_capacity(biggest_len);
-    for i in 1..biggest_len {
+    for i in 1..=biggest_len {
         groups.push(WordGroup::empty(i))
     }
 
     for word in words {
         let group = groups.get_mut(word.len() - 1).unwrap();
-        group.blob.push_str(&word);
+        group.blob.to_mut().push_str(&word);
         group.count += 1;
     }
 
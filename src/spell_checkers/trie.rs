@@ -0,0 +1,176 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    MatchAlgorithm, Suggestion, WordId,
+    matching::{LevenshteinAutomaton, edit_script},
+    spell_checkers::{SpellCheckerTrait, simple_len_group::WordGroup},
+};
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct TrieNode {
+    /// `(byte, node)` pairs, sorted by byte.
+    children: Vec<(u8, u32)>,
+    /// Set if a word ends in this node.
+    word: Option<WordId>,
+}
+
+/// Spell checker over a byte trie of all words.
+///
+/// Words are still stored in length groups, the trie points into them.
+/// Unlike the groups, the trie keeps words with the same prefix together,
+/// so suggestions are found by walking it together with a [`LevenshteinAutomaton`], skipping every branch that can't match.
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SpellChecker {
+    groups: Vec<WordGroup>,
+    /// Nodes of the trie, the first one is the root.
+    nodes: Vec<TrieNode>,
+}
+
+impl SpellChecker {
+    pub fn new(groups: Vec<WordGroup>) -> Self {
+        let mut checker = Self {
+            groups,
+            nodes: vec![TrieNode::default()],
+        };
+        for gi in 0..checker.groups.len() {
+            let len = checker.groups[gi].len;
+            for offset in (0..checker.groups[gi].blob.len()).step_by(len) {
+                checker.insert(WordId { len, offset });
+            }
+        }
+        checker
+    }
+
+    /// Groups of words the checker was built over.
+    pub(crate) fn groups_mut(&mut self) -> &mut [WordGroup] {
+        &mut self.groups
+    }
+
+    fn insert(&mut self, id: WordId) {
+        let mut current = 0;
+        for i in 0..id.len {
+            let byte = self.groups[id.len - 1].blob.as_bytes()[id.offset + i];
+            current = match self.nodes[current]
+                .children
+                .binary_search_by_key(&byte, |(b, _)| *b)
+            {
+                Ok(pos) => self.nodes[current].children[pos].1 as usize,
+                Err(pos) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(TrieNode::default());
+                    self.nodes[current]
+                        .children
+                        .insert(pos, (byte, child as u32));
+                    child
+                }
+            };
+        }
+        self.nodes[current].word = Some(id);
+    }
+
+    /// Walks the trie along the `word`, returning the node it ends in.
+    fn walk(&self, word: &[u8]) -> Option<&TrieNode> {
+        let mut node = &self.nodes[0];
+        for byte in word {
+            let pos = node.children.binary_search_by_key(byte, |(b, _)| *b).ok()?;
+            node = &self.nodes[node.children[pos].1 as usize];
+        }
+        Some(node)
+    }
+
+    /// Finds all words that are at most `max_dif` `deletions`, `insertions`, `substitutions` or transpositions away from the `word`.
+    ///
+    /// Only branches of the trie the automaton can still match are visited.
+    /// Suggestions are returned in lexicographic order.
+    pub fn suggest_for_word(&self, word: &[u8], max_dif: usize) -> Vec<Suggestion<'_>> {
+        let automaton = LevenshteinAutomaton::new(word, max_dif);
+        let mut result = vec![];
+
+        let mut stack = vec![(0usize, automaton.start())];
+        while let Some((current, state)) = stack.pop() {
+            let node = &self.nodes[current];
+            if let Some(id) = node.word
+                && automaton.distance(&state).is_some()
+            {
+                let candidate = self.get_unchecked(id);
+                let edits = edit_script(word, candidate.as_bytes(), MatchAlgorithm::Osa);
+                result.push(Suggestion {
+                    word: candidate.into(),
+                    id,
+                    distance: edits.len(),
+                    frequency: self.groups[id.len - 1].frequency(id.offset),
+                    edits,
+                });
+            }
+
+            // Reversed, so the smallest byte is popped first
+            for (byte, child) in node.children.iter().rev() {
+                let next = automaton.step(&state, *byte);
+                if automaton.can_match(&next) {
+                    stack.push((*child as usize, next));
+                }
+            }
+        }
+        result
+    }
+}
+
+impl SpellCheckerTrait for SpellChecker {
+    fn get(&self, word: WordId) -> Option<&str> {
+        let wg = self.groups.get(word.len.checked_sub(1)?)?;
+        wg.blob.get(word.offset..word.offset + word.len)
+    }
+
+    fn get_unchecked(&self, word: WordId) -> &str {
+        let lg = self
+            .groups
+            .get(word.len - 1)
+            .unwrap_or_else(|| panic!("LenGroup of len {} should exist", word.len));
+        &lg.blob[word.offset..word.offset + word.len]
+    }
+
+    fn check(&self, word: &str) -> bool {
+        self.find(word).is_some()
+    }
+
+    fn find(&self, word: &str) -> Option<WordId> {
+        self.walk(word.to_lowercase().as_bytes())?.word
+    }
+
+    fn frequency(&self, word: WordId) -> u32 {
+        word.len
+            .checked_sub(1)
+            .and_then(|i| self.groups.get(i))
+            .map_or(0, |wg| wg.frequency(word.offset))
+    }
+
+    fn suggest_for_word(&self, word: &str, max_dif: usize) -> Vec<Suggestion<'_>> {
+        SpellChecker::suggest_for_word(self, word.as_bytes(), max_dif)
+    }
+
+    fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        let prefix = prefix.to_lowercase();
+        let Some(node) = self.walk(prefix.as_bytes()) else {
+            return vec![];
+        };
+
+        // Every word below the node starts with the prefix, visited in lexicographic order
+        let mut result = vec![];
+        let mut stack = vec![node];
+        while let Some(node) = stack.pop() {
+            if let Some(id) = node.word {
+                result.push(self.get_unchecked(id));
+                if result.len() == limit {
+                    break;
+                }
+            }
+            stack.extend(
+                node.children
+                    .iter()
+                    .rev()
+                    .map(|(_, child)| &self.nodes[*child as usize]),
+            );
+        }
+        result
+    }
+}
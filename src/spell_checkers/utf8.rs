@@ -1,45 +1,274 @@
+use std::{cmp::Ordering, collections::HashMap};
+
+use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{WordId, spell_checkers::{SpellCheckerTrait, simple_len_group::WordGroup}};
+use crate::{
+    BkTree, MatchAlgorithm, Suggestion, SuggestionIndex, WordBlob, WordId, edit_script, indexes,
+    spell_checkers::SpellCheckerTrait,
+};
+
+/// Words with the same amount of chars.
+///
+/// Unlike byte length groups, words of a group can take a different amount of bytes,
+/// so the start of each word in the blob is stored.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LenGroup {
+    /// All words of the group concatenated together, sorted.
+    pub blob: String,
+    /// Byte offset of each word in the blob.
+    starts: Vec<u32>,
+    /// Amount of chars in each word.
+    pub len: usize,
+    /// Frequency of each word in the blob, empty if the dataset has none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub freq: Vec<u32>,
+}
+
+impl LenGroup {
+    pub fn empty(len: usize) -> Self {
+        Self {
+            blob: String::new(),
+            starts: vec![],
+            len,
+            freq: vec![],
+        }
+    }
+
+    fn push(&mut self, word: &str) {
+        self.starts.push(self.blob.len() as u32);
+        self.blob.push_str(word);
+    }
+
+    /// Amount of words in the group.
+    pub fn count(&self) -> usize {
+        self.starts.len()
+    }
+
+    /// Gets the word at `index`.
+    pub fn get(&self, index: usize) -> Option<&str> {
+        let start = *self.starts.get(index)? as usize;
+        let end = self
+            .starts
+            .get(index + 1)
+            .map_or(self.blob.len(), |end| *end as usize);
+        Some(&self.blob[start..end])
+    }
+
+    /// Index of the word starting at byte `offset`.
+    pub(crate) fn index_of(&self, offset: usize) -> Option<usize> {
+        self.starts.binary_search(&(offset as u32)).ok()
+    }
+
+    /// Finds the index of a word in the group using binary search, the `word` has to be lowercase.
+    pub fn find(&self, word: &str) -> Option<usize> {
+        let (mut low, mut high) = (0, self.count());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            match word.cmp(self.get(mid)?) {
+                Ordering::Equal => return Some(mid),
+                Ordering::Less => high = mid,
+                Ordering::Greater => low = mid + 1,
+            }
+        }
+        None
+    }
+
+    /// Gets the frequency of the word at `index`, `0` if the dataset has none.
+    pub fn frequency(&self, index: usize) -> u32 {
+        self.freq.get(index).copied().unwrap_or(0)
+    }
+
+    /// Sets the frequency of each word from `frequencies`, words missing from it get `0`.
+    pub fn set_frequencies(&mut self, frequencies: &HashMap<String, u32>) {
+        self.freq = (0..self.count())
+            .map(|i| {
+                self.get(i)
+                    .and_then(|w| frequencies.get(w))
+                    .copied()
+                    .unwrap_or(0)
+            })
+            .collect();
+    }
+}
+
+impl WordBlob for LenGroup {
+    fn blob(&self) -> &[u8] {
+        self.blob.as_bytes()
+    }
+
+    fn word_len(&self) -> usize {
+        self.len
+    }
+
+    fn words_count(&self) -> usize {
+        self.count()
+    }
+
+    fn word(&self, index: usize) -> &[u8] {
+        self.get(index).unwrap_or_default().as_bytes()
+    }
+}
+
+/// Groups `words` by their amount of chars, the same way `words_to_groups` groups them by bytes.
+pub fn words_to_char_groups(words: Vec<String>) -> Option<Vec<LenGroup>> {
+    let mut words: Vec<(usize, String)> = words
+        .par_iter()
+        .filter(|w| !w.is_empty())
+        .map(|w| {
+            let w = w.to_lowercase();
+            (w.chars().count(), w)
+        })
+        .collect();
+    if words.is_empty() {
+        return None;
+    }
+
+    words.par_sort_unstable();
+    words.dedup();
+    let biggest_len = words.last().unwrap().0;
 
-pub struct LenGroup {}
+    let mut groups: Vec<LenGroup> = (1..=biggest_len).map(LenGroup::empty).collect();
+    for (len, word) in words {
+        groups[len - 1].push(&word);
+    }
+    Some(groups)
+}
 
+/// Spell checker for any utf-8 words.
+///
+/// Words are grouped by their amount of chars, and matched char by char,
+/// so a typo in a multi byte char (Cyrillic, Greek, accented letters) is still a single edit.
+/// Positions of suggestion `edits` count chars.
 #[derive(Debug, Serialize, Deserialize)]
 pub struct SpellChecker {
-    groups: Vec<WordGroup>,
+    groups: Vec<LenGroup>,
+    /// Index used for suggestions, saved together with the groups.
+    #[serde(default)]
+    index: SuggestionIndex,
 }
 
 impl SpellChecker {
-    pub fn new(groups: Vec<WordGroup>) -> Self {
+    pub fn new(groups: Vec<LenGroup>) -> Self {
         Self {
-            groups
+            groups,
+            index: SuggestionIndex::default(),
         }
     }
+
+    /// Builds a `BkTree` over all words and uses it for suggestions.
+    ///
+    /// The tree measures distances in chars, the same way words are matched.
+    pub fn build_bk_tree(&mut self) -> &mut Self {
+        self.index = SuggestionIndex::BkTree(BkTree::over_chars(&self.groups));
+        self
+    }
+
+    /// Groups of words the checker was built over.
+    pub(crate) fn groups(&self) -> &[LenGroup] {
+        &self.groups
+    }
+
+    /// Groups of words the checker was built over.
+    pub(crate) fn groups_mut(&mut self) -> &mut [LenGroup] {
+        &mut self.groups
+    }
 }
 
 impl SpellCheckerTrait for SpellChecker {
     fn get(&self, word: WordId) -> Option<&str> {
-        let wg = self.groups.get(word.len)?;
-        if word.offset >= wg.blob.len() {
-            None
-        } else {
-            Some(&wg.blob[word.offset..word.offset + word.len])
-        }
+        let group = self.groups.get(word.len.checked_sub(1)?)?;
+        group.get(group.index_of(word.offset)?)
     }
 
     fn get_unchecked(&self, word: WordId) -> &str {
-        let lg = self
+        let group = self
             .groups
-            .get(word.len)
-            .expect(&format!("LenGroup of len {} should exist", word.len));
-        &lg.blob[word.offset..word.offset + word.len]
+            .get(word.len - 1)
+            .unwrap_or_else(|| panic!("LenGroup of len {} should exist", word.len));
+        let index = group
+            .index_of(word.offset)
+            .unwrap_or_else(|| panic!("Word should start at offset {}", word.offset));
+        group.get(index).unwrap()
     }
 
     fn check(&self, word: &str) -> bool {
-        let group = self.groups.get(word.len());
-        match group {
-            Some(wg) => wg.check(word),
-            None => false,
+        self.find(word).is_some()
+    }
+
+    /// Finds the `WordId` of a word, `len` is the amount of chars and `offset` the byte offset in the group.
+    fn find(&self, word: &str) -> Option<WordId> {
+        let word = word.to_lowercase();
+        let len = word.chars().count();
+        let group = self.groups.get(len.checked_sub(1)?)?;
+        let index = group.find(&word)?;
+        Some(WordId {
+            len,
+            offset: group.starts[index] as usize,
+        })
+    }
+
+    /// Finds all words that are at most `max_dif` `deletions`, `insertions`, `substitutions` or transpositions of chars away from the `word`.
+    ///
+    /// Only the groups with at most `max_dif` chars more or less are searched, or only the candidates of the `BkTree` if it was built.
+    /// Suggestions are returned in the same order as the groups.
+    fn suggest_for_word(&self, word: &str, max_dif: usize) -> Vec<Suggestion<'_>> {
+        let lowercase = word.to_lowercase();
+        let word: Vec<char> = lowercase.chars().collect();
+        if word.is_empty() {
+            return vec![];
+        }
+
+        let matches = |gi: usize, wi: usize| -> Option<Suggestion<'_>> {
+            let group = &self.groups[gi];
+            let candidate_str = group.get(wi)?;
+            let candidate: Vec<char> = candidate_str.chars().collect();
+            MatchAlgorithm::Osa.distance(&word, &candidate, max_dif)?;
+            let edits = edit_script(&word, &candidate, MatchAlgorithm::Osa);
+            Some(Suggestion {
+                word: candidate_str.into(),
+                id: WordId {
+                    len: group.len,
+                    offset: group.starts[wi] as usize,
+                },
+                distance: edits.len(),
+                frequency: group.frequency(wi),
+                edits,
+            })
+        };
+
+        if let Some(candidates) = self
+            .index
+            .candidates(&self.groups, lowercase.as_bytes(), max_dif)
+        {
+            return candidates
+                .into_par_iter()
+                .filter_map(|(gi, wi)| matches(gi, wi))
+                .collect();
         }
+
+        let min_len = word.len().saturating_sub(max_dif).max(1);
+        let max_len = (word.len() + max_dif).min(self.groups.len());
+        (min_len..=max_len)
+            .into_par_iter()
+            .flat_map(|len| {
+                (0..self.groups[len - 1].count())
+                    .into_par_iter()
+                    .filter_map(move |wi| matches(len - 1, wi))
+            })
+            .collect()
+    }
+
+    fn frequency(&self, word: WordId) -> u32 {
+        let Some(group) = word.len.checked_sub(1).and_then(|i| self.groups.get(i)) else {
+            return 0;
+        };
+        group
+            .index_of(word.offset)
+            .map_or(0, |index| group.frequency(index))
+    }
+
+    fn complete(&self, prefix: &str, limit: usize) -> Vec<&str> {
+        indexes::complete_words(&self.groups, prefix, limit)
     }
 }
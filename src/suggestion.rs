@@ -0,0 +1,31 @@
+use std::borrow::Cow;
+
+use crate::{WordId, matching::Edit};
+
+/// A word suggested by the `SpellChecker` for a misspelled word.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion<'a> {
+    /// The suggested word, borrowed from the dataset unless it was generated, like an inflected form of a stem.
+    pub word: Cow<'a, str>,
+    /// Id of the suggested word in the dataset.
+    pub id: WordId,
+    /// Real distance between the checked word and the suggestion, equal to the amount of `edits`.
+    pub distance: usize,
+    /// Frequency of the suggested word, `0` if the dataset has none.
+    pub frequency: u32,
+    /// Operations that turn the checked word into the suggestion, ordered by position.
+    pub edits: Vec<Edit>,
+}
+
+impl<'a> Suggestion<'a> {
+    /// Creates a `Suggestion` for a word found in the dataset as is.
+    pub fn exact(word: &'a str, id: WordId, frequency: u32) -> Self {
+        Self {
+            word: word.into(),
+            id,
+            distance: 0,
+            frequency,
+            edits: vec![],
+        }
+    }
+}
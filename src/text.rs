@@ -0,0 +1,262 @@
+use std::ops::Range;
+
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::Suggestion;
+
+/// How running text of a language is split into words to check.
+///
+/// Companion of [`crate::Language`], each language returns its rules from `tokenizer()`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TokenizerRules {
+    /// Keeps hyphenated words like `well-known` together, they're correct if the whole word or each of its parts is.
+    pub join_hyphenated: bool,
+    /// Skips words with digits, like `3rd` or `mp3`. Numbers are always skipped.
+    pub skip_digits: bool,
+    /// Endings after an apostrophe that are checked apart from the word they're attached to, like the English `'s` of `dog's`.
+    pub clitics: &'static [&'static str],
+}
+
+impl Default for TokenizerRules {
+    fn default() -> Self {
+        Self {
+            join_hyphenated: true,
+            skip_digits: true,
+            clitics: &[],
+        }
+    }
+}
+
+/// A word found in running text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Token<'a> {
+    pub word: &'a str,
+    /// Byte offsets of the word in the text.
+    pub span: Range<usize>,
+    /// Char offsets of the word in the text.
+    pub char_span: Range<usize>,
+}
+
+impl<'a> Token<'a> {
+    /// Splits a hyphenated word into its parts, with their own offsets.
+    pub fn parts(&self) -> Vec<Token<'a>> {
+        let mut parts = vec![];
+        let mut start = 0;
+        for part in self.word.split('-') {
+            parts.push(self.slice(start..start + part.len()));
+            start += part.len() + 1;
+        }
+        parts
+    }
+
+    /// Splits an identifier into its parts, with their own offsets, see `identifier_parts()`.
+    ///
+    /// Parts of a single char, like the `x` of `getX`, are left out.
+    pub fn identifier_parts(&self) -> Vec<Token<'a>> {
+        identifier_parts(self.word)
+            .into_iter()
+            .map(|part| self.slice(part))
+            .filter(|part| part.char_span.len() > 1)
+            .collect()
+    }
+
+    /// The part of the word at the byte `range` of it.
+    fn slice(&self, range: Range<usize>) -> Token<'a> {
+        let word = &self.word[range.clone()];
+        let char_start = self.char_span.start + self.word[..range.start].chars().count();
+        Token {
+            word,
+            span: self.span.start + range.start..self.span.start + range.end,
+            char_span: char_start..char_start + word.chars().count(),
+        }
+    }
+}
+
+/// A misspelled word found in running text, see [`crate::SC::check_text()`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Misspelling<'t, 's> {
+    /// The misspelled word, as written in the text.
+    pub word: &'t str,
+    /// Byte offsets of the word in the text.
+    pub span: Range<usize>,
+    /// Char offsets of the word in the text, to underline it in editors counting chars.
+    pub char_span: Range<usize>,
+    /// Suggestions for the word, empty unless they were asked for.
+    pub suggestions: Vec<Suggestion<'s>>,
+}
+
+impl<'t> Misspelling<'t, '_> {
+    pub fn new(token: Token<'t>) -> Self {
+        Self {
+            word: token.word,
+            span: token.span,
+            char_span: token.char_span,
+            suggestions: vec![],
+        }
+    }
+}
+
+impl TokenizerRules {
+    /// Splits the `text` into words on Unicode word boundaries (UAX #29), leaving out spaces, punctuation and numbers.
+    ///
+    /// Apostrophes between letters stay inside words, like `don't`.
+    pub fn tokenize<'a>(&self, text: &'a str) -> Vec<Token<'a>> {
+        let segments: Vec<(usize, &str)> = text.split_word_bound_indices().collect();
+        let mut tokens = vec![];
+        // Chars are counted incrementally from the end of the previous word
+        let mut last_end = 0;
+        let mut last_char_end = 0;
+        let mut i = 0;
+        while i < segments.len() {
+            let (start, segment) = segments[i];
+            i += 1;
+            if !is_word(segment) {
+                continue;
+            }
+            let mut end = start + segment.len();
+            while self.join_hyphenated
+                && i + 1 < segments.len()
+                && segments[i].1 == "-"
+                && is_word(segments[i + 1].1)
+            {
+                let (next, segment) = segments[i + 1];
+                end = next + segment.len();
+                i += 2;
+            }
+
+            let word = &text[start..end];
+            if self.skip_digits && word.chars().any(char::is_numeric) {
+                continue;
+            }
+            let char_start = last_char_end + text[last_end..start].chars().count();
+            let char_end = char_start + word.chars().count();
+            tokens.push(Token {
+                word,
+                span: start..end,
+                char_span: char_start..char_end,
+            });
+            last_end = end;
+            last_char_end = char_end;
+        }
+        tokens
+    }
+
+    /// Same as `tokenize()`, over the given byte `ranges` of the `text` only, offsets being in the whole `text`.
+    ///
+    /// The `ranges` have to be sorted and not overlap.
+    pub fn tokenize_ranges<'a>(&self, text: &'a str, ranges: &[Range<usize>]) -> Vec<Token<'a>> {
+        let mut tokens = vec![];
+        let mut last_end = 0;
+        let mut last_char_end = 0;
+        for range in ranges {
+            let char_start = last_char_end + text[last_end..range.start].chars().count();
+            last_end = range.start;
+            last_char_end = char_start;
+            let piece_tokens = self.tokenize(&text[range.clone()]);
+            tokens.extend(piece_tokens.into_iter().map(|token| Token {
+                span: range.start + token.span.start..range.start + token.span.end,
+                char_span: char_start + token.char_span.start..char_start + token.char_span.end,
+                ..token
+            }));
+        }
+        tokens
+    }
+
+    /// Splits the lowercase `word` into the part before its clitic and the clitic, for each of the `clitics` it ends with.
+    ///
+    /// Clitics can end with one another, like `n't` and `'t`, so `can't` is split both as `ca` `n't` and `can` `'t`.
+    /// Typographic apostrophes must already be replaced with `'`.
+    pub fn split_clitics<'a>(&self, word: &'a str) -> impl Iterator<Item = (&'a str, &'static str)> + 'a {
+        self.clitics.iter().filter_map(move |&clitic| {
+            let stem = word.strip_suffix(clitic)?;
+            (!stem.is_empty()).then_some((stem, clitic))
+        })
+    }
+}
+
+/// Splits source `code` into the parts of its identifiers, see `identifier_parts()`.
+///
+/// Identifiers are runs of letters, digits and underscores, parts of a single char like the `x` of `getX` are left out.
+pub fn tokenize_code(code: &str) -> Vec<Token<'_>> {
+    let mut tokens = vec![];
+    let mut last_end = 0;
+    let mut last_char_end = 0;
+    let mut identifier_start = None;
+    // A trailing space ends the last identifier
+    for (at, c) in code.char_indices().chain([(code.len(), ' ')]) {
+        if c.is_alphanumeric() || c == '_' {
+            identifier_start.get_or_insert(at);
+        } else if let Some(start) = identifier_start.take() {
+            let char_start = last_char_end + code[last_end..start].chars().count();
+            let identifier = Token {
+                word: &code[start..at],
+                span: start..at,
+                char_span: char_start..char_start + code[start..at].chars().count(),
+            };
+            last_end = at;
+            last_char_end = identifier.char_span.end;
+            tokens.extend(identifier.identifier_parts());
+        }
+    }
+    tokens
+}
+
+/// Splits an `identifier` into the byte ranges of the words it's made of.
+///
+/// Parts are split on underscores, digits and case changes, with acronyms kept together,
+/// like `parse`, `HTTP` and `Response` for `parseHTTPResponse2`, or `MAX` and `VALUE` for `MAX_VALUE`.
+pub fn identifier_parts(identifier: &str) -> Vec<Range<usize>> {
+    let chars: Vec<(usize, char)> = identifier.char_indices().collect();
+    let mut parts = vec![];
+    let mut start = None;
+    for (i, &(at, c)) in chars.iter().enumerate() {
+        if !c.is_alphabetic() {
+            if let Some(start) = start.take() {
+                parts.push(start..at);
+            }
+            continue;
+        }
+        if let Some(part_start) = start
+            && c.is_uppercase()
+        {
+            let prev = chars[i - 1].1;
+            let next_is_lower = chars
+                .get(i + 1)
+                .is_some_and(|(_, next)| next.is_lowercase());
+            // The `H` of `parseHttp`, or the `R` of `HTTPResponse`
+            if prev.is_lowercase() || (prev.is_uppercase() && next_is_lower) {
+                parts.push(part_start..at);
+                start = Some(at);
+            }
+        }
+        start.get_or_insert(at);
+    }
+    if let Some(start) = start {
+        parts.push(start..identifier.len());
+    }
+    parts
+}
+
+/// Writes the lowercase `word` in the casing of the word it replaces, all uppercase, capitalized or as is.
+pub(crate) fn match_case(word: &str, like: &str) -> String {
+    let mut letters = like.chars().filter(|c| c.is_alphabetic());
+    let Some(first) = letters.next() else {
+        return word.to_owned();
+    };
+    if !first.is_uppercase() {
+        return word.to_owned();
+    }
+    if letters.clone().next().is_some() && letters.all(char::is_uppercase) {
+        return word.to_uppercase();
+    }
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+/// Segments with a letter are words, others are spaces, punctuation or numbers.
+fn is_word(segment: &str) -> bool {
+    segment.chars().any(char::is_alphabetic)
+}
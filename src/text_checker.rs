@@ -0,0 +1,210 @@
+use std::{collections::HashSet, fs, io, ops::Range, path::Path};
+
+use rayon::prelude::*;
+
+use crate::{
+    Diagnostic, Dictionary, Markup, Misspelling, SourceLanguage, Suggestion, Token, TokenizerRules,
+    text::{match_case, tokenize_code},
+};
+
+/// Checks running text, source code and files against any `Dictionary`, split into words by the given rules.
+///
+/// `SC` checks them with the rules of its language, see [`crate::SC::check_text()`].
+/// The old `SpellChecker` and a `DictionaryStack` can check them too, with the rules of the language they're of,
+/// like `TextChecker::new(&checker, English::tokenizer())`.
+pub struct TextChecker<'d, D: Dictionary + ?Sized> {
+    dictionary: &'d D,
+    rules: TokenizerRules,
+}
+
+impl<'d, D: Dictionary + ?Sized> TextChecker<'d, D> {
+    pub fn new(dictionary: &'d D, rules: TokenizerRules) -> Self {
+        Self { dictionary, rules }
+    }
+
+    /// Finds the misspelled words of running `text`, without suggestions, see [`crate::SC::check_text()`].
+    pub fn check_text<'t>(&self, text: &'t str) -> Vec<Misspelling<'t, 'd>> {
+        self.misspelled_tokens(self.rules.tokenize(text), false)
+            .into_iter()
+            .map(Misspelling::new)
+            .collect()
+    }
+
+    /// Same as `check_text()`, with the first `take_first_x` suggestions for each misspelling, keeping its clitic.
+    pub fn suggest_text<'t>(&self, text: &'t str, take_first_x: usize) -> Vec<Misspelling<'t, 'd>> {
+        self.misspelled_tokens(self.rules.tokenize(text), false)
+            .into_par_iter()
+            .map(|token| Misspelling {
+                suggestions: self.suggest_token(token.word, take_first_x),
+                ..Misspelling::new(token)
+            })
+            .collect()
+    }
+
+    /// Finds the misspelled parts of the identifiers of source `code`, see [`crate::SC::check_code()`].
+    pub fn check_code<'t>(&self, code: &'t str) -> Vec<Misspelling<'t, 'd>> {
+        self.misspelled_tokens(tokenize_code(code), false)
+            .into_iter()
+            .map(Misspelling::new)
+            .collect()
+    }
+
+    /// Same as `check_code()`, with the first `take_first_x` suggestions for each misspelling, in its casing.
+    pub fn suggest_code<'t>(&self, code: &'t str, take_first_x: usize) -> Vec<Misspelling<'t, 'd>> {
+        self.misspelled_tokens(tokenize_code(code), false)
+            .into_par_iter()
+            .map(|token| Misspelling {
+                suggestions: self
+                    .dictionary
+                    .suggest(token.word, take_first_x)
+                    .into_iter()
+                    .map(|suggestion| Suggestion {
+                        word: match_case(&suggestion.word, token.word).into(),
+                        ..suggestion
+                    })
+                    .collect(),
+                ..Misspelling::new(token)
+            })
+            .collect()
+    }
+
+    /// Same as `check_text()`, over the given byte `ranges` of the `text` only, see [`crate::SC::check_ranges()`].
+    pub fn check_ranges<'t>(
+        &self,
+        text: &'t str,
+        ranges: &[Range<usize>],
+    ) -> Vec<Misspelling<'t, 'd>> {
+        self.misspelled_tokens(self.rules.tokenize_ranges(text, ranges), true)
+            .into_iter()
+            .map(Misspelling::new)
+            .collect()
+    }
+
+    /// Same as `check_ranges()`, with the first `take_first_x` suggestions for each misspelling, in its casing.
+    pub fn suggest_ranges<'t>(
+        &self,
+        text: &'t str,
+        ranges: &[Range<usize>],
+        take_first_x: usize,
+    ) -> Vec<Misspelling<'t, 'd>> {
+        self.misspelled_tokens(self.rules.tokenize_ranges(text, ranges), true)
+            .into_par_iter()
+            .map(|token| Misspelling {
+                suggestions: self
+                    .suggest_token(token.word, take_first_x)
+                    .into_iter()
+                    .map(|suggestion| Suggestion {
+                        word: match_case(&suggestion.word, token.word).into(),
+                        ..suggestion
+                    })
+                    .collect(),
+                ..Misspelling::new(token)
+            })
+            .collect()
+    }
+
+    /// Checks the prose of a `file`, with its format picked by its extension, see [`crate::SC::check_file()`].
+    pub fn check_file(
+        &self,
+        file: impl AsRef<Path>,
+        take_first_x: usize,
+    ) -> io::Result<Vec<Diagnostic<'d>>> {
+        let file = file.as_ref();
+        let language = SourceLanguage::from_path(file);
+        let markup = Markup::from_path(file);
+        if language.is_none() && markup.is_none() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("unsupported file type: {}", file.display()),
+            ));
+        }
+        let source = fs::read_to_string(file)?;
+        let ranges = match language {
+            Some(language) => language.extract(&source),
+            None => markup
+                .map(|markup| markup.extract(&source))
+                .unwrap_or_default(),
+        };
+        let misspellings = self.suggest_ranges(&source, &ranges, take_first_x);
+        Ok(Diagnostic::from_misspellings(file, &source, misspellings))
+    }
+
+    /// The `tokens` that aren't correct, misspelled parts of hyphenated words on their own.
+    ///
+    /// With `split_identifiers`, misspelled parts of identifiers are on their own too.
+    fn misspelled_tokens<'t>(
+        &self,
+        tokens: Vec<Token<'t>>,
+        split_identifiers: bool,
+    ) -> Vec<Token<'t>> {
+        let mut misspelled = vec![];
+        for token in tokens {
+            if self.check_token(token.word) {
+                continue;
+            }
+            if token.word.contains('-') {
+                misspelled.extend(
+                    token
+                        .parts()
+                        .into_iter()
+                        .filter(|part| !self.check_token(part.word)),
+                );
+            } else if split_identifiers {
+                misspelled.extend(
+                    token
+                        .identifier_parts()
+                        .into_iter()
+                        .filter(|part| !self.check_token(part.word)),
+                );
+            } else {
+                misspelled.push(token);
+            }
+        }
+        misspelled
+    }
+
+    /// Checks a word of running text, with typographic apostrophes and clitics.
+    ///
+    /// The word is correct if the stem of any of its clitics is.
+    fn check_token(&self, word: &str) -> bool {
+        let word = word.to_lowercase().replace('’', "'");
+        self.dictionary.check(&word)
+            || self
+                .rules
+                .split_clitics(&word)
+                .any(|(stem, _)| self.dictionary.check(stem))
+    }
+
+    /// Suggests words for a word of running text, keeping its clitic.
+    ///
+    /// Suggestions for the stems of several clitics it ends with are merged, closest first.
+    fn suggest_token(&self, word: &str, take_first_x: usize) -> Vec<Suggestion<'d>> {
+        let word = word.to_lowercase().replace('’', "'");
+        let splits: Vec<(&str, &str)> = self.rules.split_clitics(&word).collect();
+        if splits.is_empty() {
+            return self.dictionary.suggest(&word, take_first_x);
+        }
+        let merged = splits.len() > 1;
+        let mut seen = HashSet::new();
+        let mut suggestions: Vec<Suggestion> = splits
+            .into_iter()
+            .flat_map(|(stem, clitic)| {
+                self.dictionary
+                    .suggest(stem, take_first_x)
+                    .into_iter()
+                    .map(move |suggestion| Suggestion {
+                        word: format!("{}{clitic}", suggestion.word).into(),
+                        ..suggestion
+                    })
+            })
+            .filter(|suggestion| seen.insert(suggestion.word.clone()))
+            .collect();
+        if merged {
+            suggestions.sort_by_key(|suggestion| suggestion.distance);
+        }
+        if take_first_x != 0 {
+            suggestions.truncate(take_first_x);
+        }
+        suggestions
+    }
+}
@@ -0,0 +1,126 @@
+#[cfg(test)]
+mod affix_tests {
+    use spel_right::{
+        English, Hunspell, SC, SpellCheckerBuilder,
+        spell_checkers::{SpellCheckerTrait, affix},
+    };
+
+    static AFF: &str = "SET UTF-8
+NEEDAFFIX X
+FORBIDDENWORD F
+
+PFX A Y 1
+PFX A   0     re         .
+
+SFX B Y 3
+SFX B   0     ed         [^y]
+SFX B   0     ed         [aeiou]y
+SFX B   y     ied        [^aeiou]y
+
+SFX S N 2
+SFX S   0     s          [^s]
+SFX S   0     es         s
+
+SFX C Y 1
+SFX C   0     ful/NX     .
+
+SFX N Y 1
+SFX N   0     ness       .
+";
+
+    static DIC: &str = "7
+work/AB
+try/B
+play/BS
+bus/S
+care/C
+meaning/C
+badword/SF
+";
+
+    fn dict() -> Hunspell {
+        Hunspell::parse(DIC, AFF).unwrap()
+    }
+
+    #[test]
+    fn accepts_expanded_words() {
+        let dict = dict();
+        let checker = affix::SpellChecker::new(&dict).unwrap();
+        for word in dict.expand() {
+            assert!(checker.check(&word), "{word}");
+        }
+        assert!(checker.check("Reworked"));
+
+        for word in [
+            "workied",
+            "busses",
+            "buss",
+            "replay",
+            "careful",
+            "meaningful",
+            "badword",
+            "badwords",
+            "reworks",
+            "triedness",
+            "",
+        ] {
+            assert!(!checker.check(word), "{word}");
+        }
+    }
+
+    #[test]
+    fn finds_only_bare_stems() {
+        let checker = affix::SpellChecker::new(&dict()).unwrap();
+        let work = checker.find("work").unwrap();
+        assert_eq!(checker.get(work), Some("work"));
+        assert!(checker.find("worked").is_none());
+        assert!(checker.find("badword").is_none());
+        assert_eq!(checker.complete("wo", 0), vec!["work"]);
+    }
+
+    #[test]
+    fn suggests_inflected_forms() {
+        let checker = affix::SpellChecker::new(&dict()).unwrap();
+        let suggestions = checker.suggest("wroked", 1, 0);
+        assert_eq!(suggestions[0].word, "worked");
+        assert_eq!(suggestions[0].distance, 1);
+        assert_eq!(checker.get(suggestions[0].id), Some("work"));
+
+        assert_eq!(checker.suggest("tryed", 1, 1)[0].word, "tried");
+        assert_eq!(checker.suggest("carfulness", 1, 1)[0].word, "carefulness");
+        // Both affixes and a typo in the stem
+        assert!(
+            checker
+                .suggest_for_word("rewroked", 1)
+                .iter()
+                .any(|s| s.word == "reworked")
+        );
+    }
+
+    #[test]
+    fn stems_in_sc() {
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_hunspell_stems(&dict())
+            .unwrap()
+            .with_bk_tree()
+            .build();
+        assert!(checker.check("plays"));
+        assert!(checker.check("meaningfulness"));
+        assert!(!checker.check("plaied"));
+
+        let exact = checker.suggest("Tried", 0);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].word, "tried");
+        assert_eq!(checker.suggest("plaied", 1)[0].word, "played");
+
+        let json = serde_json::to_string(&checker).unwrap();
+        let loaded: SC<English> = serde_json::from_str(&json).unwrap();
+        assert!(loaded.check("buses"));
+        assert!(!loaded.check("buss"));
+        let json = serde_json::to_value(&checker).unwrap();
+        assert_eq!(
+            json["dict_meta"]["included_encodings"],
+            serde_json::json!([])
+        );
+    }
+}
@@ -0,0 +1,175 @@
+mod common;
+
+#[cfg(test)]
+mod binary_dict_tests {
+    use std::panic::AssertUnwindSafe;
+
+    use filess::{Json, ModelFileTrait};
+    use spel_right::{
+        DICT_VERSION, DictMetadata, English, SC, SpellChecker, SpellCheckerBuilder,
+        load_binary_dict, load_words_dict, save_binary_dict,
+    };
+
+    use crate::common::temp_path;
+
+    static WORDS_FILE: &str = "words.txt";
+
+    fn meta() -> DictMetadata {
+        DictMetadata {
+            language_full: "English (United States)".into(),
+            language_short: "en_us".into(),
+            version: DICT_VERSION,
+            words_amount: 0,
+            included_encodings: vec!["ascii".into()],
+        }
+    }
+
+    #[test]
+    fn binary_matches_text() {
+        let file = temp_path("binary_dict_test.bin");
+        let text = SpellChecker::new(WORDS_FILE);
+        text.save_binary(&file, &meta()).unwrap();
+
+        let (loaded_meta, groups) = load_binary_dict(&file).unwrap();
+        assert_eq!(loaded_meta, meta());
+        assert_eq!(groups.len(), load_words_dict(WORDS_FILE).unwrap().len());
+
+        let binary = SpellChecker::new_binary(&file).unwrap();
+        for word in ["hello", "Function", "the", "funciton", "hellox"] {
+            assert_eq!(binary.check(word), text.check(word));
+        }
+        for word in ["teh", "nothng", "hardwear"] {
+            assert_eq!(binary.suggest(word, 5), text.suggest(word, 5));
+        }
+        assert_eq!(binary.complete("hel", 3), text.complete("hel", 3));
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn binary_frequencies() {
+        let words = temp_path("binary_freq_test.txt");
+        let file = temp_path("binary_freq_test.bin");
+        std::fs::write(&words, "3\nteathetoe\n# 5 100 3\n").unwrap();
+        let groups = load_words_dict(&words).unwrap();
+        save_binary_dict(&file, &meta(), &groups).unwrap();
+
+        let checker = SpellChecker::new_binary(&file).unwrap();
+        let the = checker.find("the").unwrap();
+        assert_eq!(checker.word_groups[2].frequency(the.offset), 100);
+        assert_eq!(checker.suggest("thx", 0)[0].word, "the");
+        assert_eq!(checker.suggest("tox", 0)[0].word, "toe");
+
+        std::fs::remove_file(&words).unwrap();
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn sc_binary_roundtrip() {
+        let file = temp_path("sc_binary_test.bin");
+        let words: Vec<String> = ["hello", "world", "help"]
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_ascii_words(words)
+            .unwrap()
+            .build();
+        checker.save_binary(&file).unwrap();
+
+        let loaded = SC::<English>::new_binary(&file).unwrap();
+        assert!(loaded.check("hello"));
+        assert!(loaded.check("help"));
+        assert!(!loaded.check("helo"));
+        assert_eq!(loaded.suggest("wrld", 1)[0].word, "world");
+        assert_eq!(loaded.complete("hel", 0), vec!["hello", "help"]);
+        std::fs::remove_file(&file).unwrap();
+
+        // Words of other checkers aren't silently dropped
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_ascii_words(vec!["cafe".to_owned()])
+            .unwrap()
+            .add_norm_words(vec!["café".to_owned()])
+            .unwrap()
+            .build();
+        assert!(checker.save_binary(&file).is_err());
+        let mut checker: SC<English> = SpellCheckerBuilder::new()
+            .add_ascii_words(vec!["cafe".to_owned()])
+            .unwrap()
+            .build();
+        checker.forbid("cafe", None);
+        assert!(checker.save_binary(&file).is_err());
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn sc_json_version_checked() {
+        let file = temp_path("sc_version_test.json");
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_ascii_words(vec!["hello".to_owned()])
+            .unwrap()
+            .build();
+        Json::new(&file).save_model(&checker).unwrap();
+        assert!(
+            SC::<English>::new_with_file(Json::new(&file))
+                .unwrap()
+                .check("hello")
+        );
+
+        let saved = std::fs::read_to_string(&file).unwrap();
+        std::fs::write(&file, saved.replacen("\"version\": 2", "\"version\": 1", 1)).unwrap();
+        let error = SC::<English>::new_with_file(Json::new(&file))
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .contains("unsupported dictionary version 1")
+        );
+
+        // A dictionary that doesn't load at all still says why
+        std::fs::write(&file, r#"{"dict_meta":{"version":3}}"#).unwrap();
+        let error = SC::<English>::new_with_file(Json::new(&file))
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .contains("unsupported dictionary version 3")
+        );
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn invalid_binary_dict() {
+        let file = temp_path("invalid_binary_test.bin");
+        std::fs::write(&file, "3\nteathetoe\n").unwrap();
+        assert!(load_binary_dict(&file).is_err());
+
+        let groups = load_words_dict(WORDS_FILE).unwrap();
+        save_binary_dict(&file, &meta(), &groups).unwrap();
+        let bytes = std::fs::read(&file).unwrap();
+        std::fs::write(&file, &bytes[..bytes.len() / 2]).unwrap();
+        assert!(load_binary_dict(&file).is_err());
+
+        let mut bytes = bytes;
+        bytes[4] = 9;
+        std::fs::write(&file, &bytes).unwrap();
+        assert!(load_binary_dict(&file).is_err());
+
+        // Blobs are only checked once they're used, so their pages aren't read on load
+        let words = temp_path("invalid_binary_test.txt");
+        std::fs::write(&words, "3\ncatdog\n").unwrap();
+        SpellChecker::new(&words)
+            .save_binary(&file, &meta())
+            .unwrap();
+        std::fs::remove_file(&words).unwrap();
+        let mut bytes = std::fs::read(&file).unwrap();
+        *bytes.last_mut().unwrap() = 0xff;
+        std::fs::write(&file, &bytes).unwrap();
+        let checker = SpellChecker::new_binary(&file).unwrap();
+        let used = std::panic::catch_unwind(AssertUnwindSafe(|| checker.check("cat")));
+        assert!(used.is_err());
+        drop(checker);
+        std::fs::remove_file(&file).unwrap();
+    }
+}
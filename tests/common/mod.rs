@@ -0,0 +1,54 @@
+//! Helpers shared by the integration tests, each test file pulls them in with `mod common;`.
+#![allow(dead_code)]
+
+use std::{path::PathBuf, str::from_utf8};
+
+use spel_right::{Language, SC, SpellCheckerBuilder, WordBlob, load_words_dict};
+
+pub static WORDS_FILE: &str = "words.txt";
+
+pub fn to_strings(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+/// All words of the `words.txt` dataset, by length and then sorted.
+pub fn dataset_words() -> Vec<String> {
+    load_words_dict(WORDS_FILE)
+        .unwrap()
+        .iter()
+        .flat_map(|group| {
+            (0..group.words_count()).map(|i| from_utf8(group.word(i)).unwrap().to_owned())
+        })
+        .collect()
+}
+
+/// A builder with the `ascii`, normalized and utf-8 words added, empty lists are skipped.
+pub fn builder<L: Language + Default>(
+    ascii: &[&str],
+    norm: &[&str],
+    utf8: &[&str],
+) -> SpellCheckerBuilder<L> {
+    let mut builder = SpellCheckerBuilder::new();
+    if !ascii.is_empty() {
+        builder.add_ascii_words(to_strings(ascii)).unwrap();
+    }
+    if !norm.is_empty() {
+        builder.add_norm_words(to_strings(norm)).unwrap();
+    }
+    if !utf8.is_empty() {
+        builder.add_utf8_words(to_strings(utf8)).unwrap();
+    }
+    builder
+}
+
+/// A checker with the `ascii`, normalized and utf-8 words, see `builder()`.
+pub fn checker<L: Language + Default>(ascii: &[&str], norm: &[&str], utf8: &[&str]) -> SC<L> {
+    builder(ascii, norm, utf8).build()
+}
+
+/// Path of a file `name` in the system temp dir, unique to this process.
+///
+/// Concurrent test runs on one machine never share files, as long as each test uses its own `name`.
+pub fn temp_path(name: &str) -> PathBuf {
+    std::env::temp_dir().join(format!("spel_right_{}_{name}", std::process::id()))
+}
@@ -0,0 +1,61 @@
+#[cfg(test)]
+mod complete_tests {
+    use spel_right::{
+        English, SC, SpellChecker, SpellCheckerBuilder,
+        spell_checkers::{SpellCheckerTrait, trie, words_to_groups},
+    };
+
+    static WORDS_FILE: &str = "words.txt";
+
+    #[test]
+    fn complete_matches_all_words() {
+        let checker = SpellChecker::new(WORDS_FILE);
+        let all = checker.complete("", 0);
+
+        for prefix in ["prog", "Hel", "z", "struct", "xqz"] {
+            let lower = prefix.to_lowercase();
+            let mut expected: Vec<&str> = all
+                .iter()
+                .copied()
+                .filter(|w| w.starts_with(&lower))
+                .collect();
+            expected.sort_unstable();
+
+            assert_eq!(checker.complete(prefix, 0), expected);
+            expected.truncate(5);
+            assert_eq!(checker.complete(prefix, 5), expected);
+        }
+    }
+
+    #[test]
+    fn complete_trie_matches_groups() {
+        let checker = SpellChecker::new(WORDS_FILE);
+        let words: Vec<String> = checker
+            .complete("", 0)
+            .iter()
+            .map(|w| w.to_string())
+            .collect();
+        let trie = trie::SpellChecker::new(words_to_groups(words).unwrap());
+
+        for prefix in ["prog", "hel", "a", "qu"] {
+            assert_eq!(trie.complete(prefix, 0), checker.complete(prefix, 0));
+            assert_eq!(trie.complete(prefix, 3), checker.complete(prefix, 3));
+        }
+    }
+
+    #[test]
+    fn complete_from_builder() {
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_utf8_words(vec!["programme".into(), "prögram".into()])
+            .unwrap()
+            .add_trie_words(vec!["program".into(), "progress".into(), "project".into()])
+            .unwrap()
+            .build();
+        assert_eq!(
+            checker.complete("prog", 0),
+            vec!["program", "programme", "progress"]
+        );
+        assert_eq!(checker.complete("prog", 2), vec!["program", "programme"]);
+        assert_eq!(checker.complete("prö", 0), vec!["prögram"]);
+    }
+}
@@ -0,0 +1,85 @@
+mod common;
+
+#[cfg(test)]
+mod compound_tests {
+    use spel_right::{CompoundRules, English, German, SC};
+
+    use crate::common;
+
+    fn checker() -> SC<German> {
+        common::checker(
+            &[
+                "donau", "dampf", "schiff", "fahrt", "arbeit", "zimmer", "haus", "an",
+            ],
+            &["tür", "küche"],
+            &[],
+        )
+    }
+
+    #[test]
+    fn checks_compounds() {
+        let checker = checker();
+        assert!(checker.check("Donaudampfschifffahrt"));
+        assert_eq!(
+            checker.split_compound("Donaudampfschifffahrt").unwrap(),
+            vec!["donau", "dampf", "schiff", "fahrt"]
+        );
+        assert_eq!(
+            checker.split_compound("Arbeitszimmer").unwrap(),
+            vec!["arbeit", "zimmer"]
+        );
+        assert_eq!(
+            checker.split_compound("Haustür").unwrap(),
+            vec!["haus", "tür"]
+        );
+        assert!(checker.check("Küchentür"));
+
+        // Not a compound, unknown parts, a trailing linking morpheme or too short parts
+        assert!(checker.split_compound("zimmer").is_none());
+        assert!(!checker.check("donauxdampf"));
+        assert!(!checker.check("zimmers"));
+        assert!(!checker.check("anhaus"));
+        assert!(!checker.check(""));
+    }
+
+    #[test]
+    fn compound_rules() {
+        let mut checker = checker();
+        checker.compounds = Some(CompoundRules {
+            min_part_len: 5,
+            linking_morphemes: &[],
+        });
+        assert!(checker.check("dampfschiff"));
+        assert!(!checker.check("haustür"));
+        assert!(!checker.check("arbeitszimmer"));
+
+        checker.compounds = None;
+        assert!(!checker.check("dampfschiff"));
+        assert!(checker.check("dampf"));
+
+        let english: SC<English> = common::checker(&["hello", "world"], &[], &[]);
+        assert!(!english.check("helloworld"));
+    }
+
+    #[test]
+    fn suggests_corrected_parts() {
+        let checker = checker();
+        let suggestions = checker.suggest("Donaudampfshifffahrt", 0);
+        assert_eq!(suggestions[0].word, "donaudampfschifffahrt");
+        assert_eq!(suggestions[0].distance, 1);
+        assert_eq!(checker.suggest("Arbeitszimmr", 1)[0].word, "arbeitszimmer");
+
+        // Long compounds only correct their shortest unknown parts
+        let long = "donaudampfschifffahrtarbeitszimmerhausdampfzimmr";
+        assert_eq!(
+            checker.suggest(long, 1)[0].word,
+            "donaudampfschifffahrtarbeitszimmerhausdampfzimmer"
+        );
+
+        let exact = checker.suggest("Arbeitszimmer", 0);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].word, "arbeitszimmer");
+        assert_eq!(exact[0].distance, 0);
+        assert_eq!(exact[0].id, checker.suggest("zimmer", 0)[0].id);
+    }
+}
@@ -0,0 +1,84 @@
+mod common;
+
+#[cfg(test)]
+mod dictionary_stack_tests {
+    use spel_right::{DictionaryStack, English, SC, SpellChecker, SpellCheckerBuilder};
+
+    use crate::common::temp_path;
+
+    fn layer(words: &[&str]) -> SC<English> {
+        SpellCheckerBuilder::new()
+            .add_ascii_words(words.iter().map(|w| w.to_string()).collect())
+            .unwrap()
+            .build()
+    }
+
+    fn stack() -> DictionaryStack {
+        let personal = temp_path("stack_personal_test.txt");
+        std::fs::write(&personal, "8\nmangahub\n").unwrap();
+        let personal_checker = SpellChecker::new(&personal);
+        std::fs::remove_file(&personal).unwrap();
+
+        let mut stack = DictionaryStack::new();
+        stack
+            .add_layer(layer(&["hello", "world", "help", "irregardless"]))
+            .add_layer(layer(&["spelright", "rustacean", "hello"]))
+            .add_layer(personal_checker)
+            .add_forbidden_layer(layer(&["irregardless"]));
+        stack
+    }
+
+    #[test]
+    fn checks_all_layers() {
+        let stack = stack();
+        assert_eq!(stack.len(), 4);
+        assert_eq!(
+            stack.batch_check(&["Hello", "SpelRight", "mangahub", "helo", "irregardless"]),
+            vec![
+                ("Hello", true),
+                ("SpelRight", true),
+                ("mangahub", true),
+                ("helo", false),
+                ("irregardless", false),
+            ]
+        );
+        assert!(stack.is_forbidden("Irregardless"));
+        assert!(!stack.is_forbidden("hello"));
+        assert!(!DictionaryStack::new().check("hello"));
+    }
+
+    #[test]
+    fn merges_suggestions() {
+        let stack = stack();
+        let exact = stack.suggest("Rustacean", 0);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].word, "rustacean");
+        assert_eq!(exact[0].distance, 0);
+
+        assert_eq!(stack.suggest("rustacan", 1)[0].word, "rustacean");
+        assert_eq!(stack.suggest("mangahb", 1)[0].word, "mangahub");
+
+        // `hello` is in two layers, but only suggested once
+        let helo = stack.suggest("helo", 0);
+        assert_eq!(helo.iter().filter(|s| s.word == "hello").count(), 1);
+        assert!(helo.iter().any(|s| s.word == "help"));
+        assert!(
+            helo.windows(2)
+                .all(|pair| pair[0].distance <= pair[1].distance)
+        );
+    }
+
+    #[test]
+    fn forbidden_words_are_never_suggested() {
+        let stack = stack();
+        assert!(
+            stack
+                .suggest("irregardles", 0)
+                .iter()
+                .all(|s| s.word != "irregardless")
+        );
+        // The forbidden word itself gets suggestions instead of being accepted
+        let suggestions = stack.suggest("irregardless", 0);
+        assert!(suggestions.iter().all(|s| s.word != "irregardless"));
+    }
+}
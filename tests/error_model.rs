@@ -0,0 +1,126 @@
+mod common;
+
+#[cfg(test)]
+mod error_model_tests {
+    use spel_right::{Edit, ErrorModel, MatchAlgorithm, Scorer, SpellChecker, Suggestion};
+
+    use crate::common::temp_path;
+
+    static WORDS_FILE: &str = "words.txt";
+
+    fn words(suggestions: &[Suggestion]) -> Vec<String> {
+        suggestions.iter().map(|s| s.word.to_string()).collect()
+    }
+
+    #[test]
+    fn default_model_keeps_ranking() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        checker.algorithm = MatchAlgorithm::Osa;
+        let words_to_check = ["tha", "funciton", "diferently", "recieve", "wrld"];
+        let expected: Vec<Vec<String>> = words_to_check
+            .iter()
+            .map(|word| words(&checker.suggest(word, 0)))
+            .collect();
+
+        checker.scorer = Box::new(ErrorModel::default());
+        for (word, expected) in words_to_check.iter().zip(expected) {
+            assert_eq!(words(&checker.suggest(word, 0)), expected);
+        }
+    }
+
+    #[test]
+    fn confusion_costs() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        checker.algorithm = MatchAlgorithm::Osa;
+        assert_ne!(checker.suggest("tham", 1)[0].word, "them");
+
+        let mut model = ErrorModel::new();
+        model.set_substitution(b'a', b'e', 0.3);
+        checker.scorer = Box::new(model);
+        assert_eq!(checker.suggest("tham", 1)[0].word, "them");
+    }
+
+    #[test]
+    fn edit_costs() {
+        let model = ErrorModel::parse(
+            "# Test model
+            insert 2
+            delete 3 extra bytes
+            substitute 4
+            transpose 5
+            frequency 0.5
+            insert e 0.5
+            delete s 0.6
+            substitute e a 0.3
+            transpose i e 0.4",
+        )
+        .unwrap();
+        assert_eq!(model.insert, 2.);
+        assert_eq!(model.delete, 3.);
+        assert_eq!(model.frequency_weight, 0.5);
+
+        let cost = |word: &str, candidate: &str, edit| {
+            model.edit_cost(word.as_bytes(), candidate.as_bytes(), edit)
+        };
+        let insert = |pos, candidate_pos| Edit::Insert { pos, candidate_pos };
+        assert_eq!(cost("th", "the", insert(2, 2)), 0.5);
+        assert_eq!(cost("te", "the", insert(1, 1)), 2.);
+        assert_eq!(cost("cats", "cat", Edit::Delete { pos: 3 }), 0.6);
+        assert_eq!(cost("catt", "cat", Edit::Delete { pos: 3 }), 3.);
+        let substitute = Edit::Substitute {
+            pos: 1,
+            candidate_pos: 1,
+        };
+        assert_eq!(cost("bet", "bat", substitute), 0.3);
+        assert_eq!(cost("bat", "bet", substitute), 4.);
+        let transpose = Edit::Transpose {
+            pos: 1,
+            candidate_pos: 1,
+        };
+        assert_eq!(cost("riecve", "reicve", transpose), 0.4);
+        assert_eq!(cost("reicve", "riecve", transpose), 5.);
+
+        let suggestion = Suggestion {
+            word: "bat".into(),
+            id: spel_right::WordId { len: 3, offset: 0 },
+            distance: 1,
+            frequency: 0,
+            edits: vec![substitute],
+        };
+        assert_eq!(model.score(b"bet", &suggestion), 0.3);
+    }
+
+    #[test]
+    fn invalid_models() {
+        for content in [
+            "insert",
+            "insert ab 1",
+            "substitute e 1",
+            "substitute e a",
+            "substitute e a x",
+            "replace e a 1",
+        ] {
+            assert!(ErrorModel::parse(content).is_err(), "{content}");
+        }
+    }
+
+    #[test]
+    fn load_model() {
+        let file = temp_path("error_model_test.txt");
+        std::fs::write(&file, "substitute a e 0.3\nsubstitute e a 0.3\n").unwrap();
+        let model = ErrorModel::load(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+        assert_eq!(
+            model.edit_cost(
+                b"a",
+                b"e",
+                Edit::Substitute {
+                    pos: 0,
+                    candidate_pos: 0
+                }
+            ),
+            0.3
+        );
+        assert!(ErrorModel::load(&file).is_err());
+    }
+}
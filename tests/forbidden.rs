@@ -0,0 +1,102 @@
+mod common;
+
+#[cfg(test)]
+mod forbidden_tests {
+    use std::collections::HashMap;
+
+    use spel_right::{English, German, SC};
+
+    use crate::common;
+
+    fn checker() -> SC<English> {
+        common::builder(
+            &[
+                "irregardless",
+                "regardless",
+                "irregular",
+                "thou",
+                "thee",
+                "the",
+                "you",
+            ],
+            &[],
+            &[],
+        )
+        .with_forbidden_words(HashMap::from([
+            ("Irregardless".to_owned(), Some("regardless".to_owned())),
+            ("thou".to_owned(), None),
+            ("whilst".to_owned(), Some("while".to_owned())),
+        ]))
+        .build()
+    }
+
+    #[test]
+    fn rejects_forbidden_words() {
+        let checker = checker();
+        assert!(!checker.check("irregardless"));
+        assert!(!checker.check("IRREGARDLESS"));
+        assert!(!checker.check("thou"));
+        assert!(checker.check("regardless"));
+        assert!(checker.is_forbidden("Thou"));
+        assert!(!checker.is_forbidden("the"));
+        assert_eq!(checker.replacement("irregardless"), Some("regardless"));
+        assert_eq!(checker.replacement("thou"), None);
+        assert_eq!(checker.replacement("the"), None);
+    }
+
+    #[test]
+    fn never_suggests_forbidden_words() {
+        let checker = checker();
+        let suggestions = checker.suggest("irregardles", 0);
+        assert!(suggestions.iter().all(|s| s.word != "irregardless"));
+        assert!(checker.suggest("tho", 0).iter().all(|s| s.word != "thou"));
+
+        let mut checker = checker;
+        checker.forbid("thee", None);
+        assert!(!checker.check("thee"));
+        assert!(checker.suggest("thex", 0).iter().all(|s| s.word != "thee"));
+    }
+
+    #[test]
+    fn suggests_replacement_first() {
+        let checker = checker();
+        let suggestions = checker.suggest("Irregardless", 0);
+        assert_eq!(suggestions[0].word, "regardless");
+        assert_eq!(suggestions[0].distance, 2);
+        assert_eq!(
+            suggestions
+                .iter()
+                .filter(|s| s.word == "regardless")
+                .count(),
+            1
+        );
+
+        // Replacements don't have to be in the dictionary
+        let whilst = checker.suggest("whilst", 1);
+        assert_eq!(whilst[0].word, "while");
+        assert_eq!(whilst[0].id.len, 0);
+
+        // Without a replacement, a forbidden word still gets suggestions
+        let thou = checker.suggest("thou", 0);
+        assert!(thou.iter().any(|s| s.word == "you"));
+    }
+
+    #[test]
+    fn never_completes_forbidden_words() {
+        let checker = checker();
+        assert!(checker.complete("irregardl", 0).is_empty());
+        assert_eq!(checker.complete("irreg", 0), vec!["irregular"]);
+        assert_eq!(checker.complete("th", 1), vec!["the"]);
+    }
+
+    #[test]
+    fn forbidden_compound_parts() {
+        let mut checker: SC<German> = common::checker(&["haus", "tier", "mist"], &[], &[]);
+        assert!(checker.check("misthaus"));
+        checker.forbid("mist", None);
+        assert!(!checker.check("misthaus"));
+        assert!(checker.check("haustier"));
+        checker.forbid("haustier", None);
+        assert!(!checker.check("haustier"));
+    }
+}
@@ -0,0 +1,90 @@
+mod common;
+
+#[cfg(test)]
+mod frequency_tests {
+    use std::collections::HashMap;
+
+    use spel_right::{
+        SpellChecker, Suggestion, load_words_dict,
+        spell_checkers::{simple_len_group::WordGroup, trie, words_to_groups},
+    };
+
+    use crate::common::temp_path;
+
+    fn checker_from(name: &str, content: &str) -> SpellChecker {
+        let file = temp_path(name);
+        std::fs::write(&file, content).unwrap();
+        let checker = SpellChecker::new(&file);
+        std::fs::remove_file(&file).unwrap();
+        checker
+    }
+
+    fn words<'a>(suggestions: &'a [Suggestion<'a>]) -> Vec<&'a str> {
+        suggestions.iter().map(|s| &*s.word).collect()
+    }
+
+    #[test]
+    fn suggest_orders_by_frequency() {
+        let checker = checker_from(
+            "frequency_test.txt",
+            "1\na\n# 7\n3\nteathetoe\n# 5 1 100\n4\ntree\n# 50",
+        );
+        let suggestions = checker.suggest("tie", 0);
+        assert_eq!(words(&suggestions), vec!["toe", "the", "tree", "tea"]);
+        assert_eq!(suggestions[0].frequency, 100);
+        assert_eq!(suggestions[1].frequency, 1);
+
+        let found = checker.suggest("tea", 0);
+        assert_eq!(found[0].frequency, 5);
+    }
+
+    #[test]
+    fn suggest_without_frequency() {
+        let checker = checker_from("no_frequency_test.txt", "3\nteathetoe\n4\ntree\n# 50");
+        let suggestions = checker.suggest("tie", 0);
+        assert_eq!(words(&suggestions), vec!["the", "toe", "tree", "tea"]);
+        assert_eq!(suggestions[3].frequency, 0);
+        assert!(suggestions[..2].iter().all(|s| s.frequency == 0));
+        assert_eq!(suggestions[2].frequency, 50);
+    }
+
+    #[test]
+    fn custom_scorer() {
+        let mut checker = checker_from("scorer_test.txt", "3\nteathetoe\n# 5 1 100\n4\ntree\n# 50");
+        // Longest words first
+        checker.scorer = Box::new(|_: &[u8], s: &Suggestion| -(s.word.len() as f64));
+        let suggestions = checker.suggest("tie", 2);
+        assert_eq!(words(&suggestions), vec!["tree", "tea"]);
+    }
+
+    #[test]
+    fn invalid_frequencies() {
+        let file = temp_path("invalid_frequency_test.txt");
+        std::fs::write(&file, "3\nteathetoe\n# 5 1").unwrap();
+        assert!(load_words_dict(&file).is_err());
+        std::fs::write(&file, "3\nteathetoe\n# 5 x 1").unwrap();
+        assert!(load_words_dict(&file).is_err());
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn word_group_frequencies() {
+        let mut groups = words_to_groups(vec!["the".into(), "toe".into(), "tea".into()]).unwrap();
+        let frequencies = HashMap::from([("the".to_owned(), 100), ("toe".to_owned(), 3)]);
+        for group in &mut groups {
+            group.set_frequencies(&frequencies);
+        }
+        assert_eq!(groups[2].freq, vec![0, 100, 3]);
+
+        let json = serde_json::to_string(&groups[2]).unwrap();
+        let group: WordGroup = serde_json::from_str(&json).unwrap();
+        assert_eq!(group.frequency(3), 100);
+        let group: WordGroup = serde_json::from_str(r#"{"blob":"the","len":3,"count":1}"#).unwrap();
+        assert_eq!(group.frequency(0), 0);
+
+        let checker = trie::SpellChecker::new(groups);
+        let suggestions = checker.suggest_for_word(b"thx", 1);
+        assert_eq!(suggestions[0].word, "the");
+        assert_eq!(suggestions[0].frequency, 100);
+    }
+}
@@ -0,0 +1,129 @@
+mod common;
+
+#[cfg(test)]
+mod hunspell_tests {
+    use spel_right::{English, Hunspell, SC, SpellCheckerBuilder, hunspell::Affixes};
+
+    use crate::common::temp_path;
+
+    static AFF: &str = "SET UTF-8
+TRY esianrtolcdugmphbyfvkwz
+
+# Prefixes
+PFX A Y 1
+PFX A   0     re         .
+
+SFX B Y 3
+SFX B   0     ed         [^y]
+SFX B   0     ed         [aeiou]y
+SFX B   y     ied        [^aeiou]y
+
+SFX S N 2
+SFX S   0     s          [^s]
+SFX S   0     es         s
+";
+
+    static DIC: &str = "4
+work/AB
+try/B
+play/BS
+bus/S
+";
+
+    #[test]
+    fn expands_affixes() {
+        let dict = Hunspell::parse(DIC, AFF).unwrap();
+        assert_eq!(dict.stems.len(), 4);
+        assert_eq!(
+            dict.expand(),
+            vec![
+                "bus", "buses", "play", "played", "plays", "rework", "reworked", "tried", "try",
+                "work", "worked",
+            ]
+        );
+    }
+
+    #[test]
+    fn flag_types_and_aliases() {
+        let aff = "FLAG long
+AF 2
+AF AaBb
+AF Bb
+PFX Aa Y 1
+PFX Aa 0 un .
+SFX Bb Y 1
+SFX Bb 0 ing .
+";
+        let dict = Hunspell::parse("2\ndo/1\nsee/2\n", aff).unwrap();
+        assert_eq!(
+            dict.expand(),
+            vec!["do", "doing", "see", "seeing", "undo", "undoing"]
+        );
+
+        let aff = "FLAG num
+SFX 101 N 1
+SFX 101 0 s .
+NEEDAFFIX 7
+";
+        let dict = Hunspell::parse("cat/101\ndog/7,101\n", aff).unwrap();
+        assert_eq!(dict.expand(), vec!["cat", "cats", "dogs"]);
+    }
+
+    #[test]
+    fn continuation_and_forbidden() {
+        let aff = "NEEDAFFIX X
+FORBIDDENWORD F
+SFX A Y 1
+SFX A 0 ful/BX .
+SFX B Y 1
+SFX B 0 ness .
+";
+        let dict = Hunspell::parse("care/A\nmeaning/A\nbadword/AF\n", aff).unwrap();
+        // `careful` needs another affix, `carefulness` has one
+        assert_eq!(
+            dict.expand(),
+            vec!["care", "carefulness", "meaning", "meaningfulness"]
+        );
+    }
+
+    #[test]
+    fn invalid_affixes() {
+        assert!(Affixes::parse("FLAG weird\n").is_err());
+        assert!(Affixes::parse("SFX A X 1\n").is_err());
+        assert!(Affixes::parse("SFX A Y 1\nSFX A 0 s [ab\n").is_err());
+        assert!(Affixes::parse("SFX A Y 1\nPFX A Y 1\n").is_err());
+        assert!(Hunspell::parse("1\nword/ABC\n", "FLAG long\n").is_err());
+    }
+
+    #[test]
+    fn loads_latin1_into_checker() {
+        let aff = temp_path("hunspell_test.aff");
+        let dic = temp_path("hunspell_test.dic");
+        std::fs::write(&aff, b"SET ISO8859-1\nSFX S Y 1\nSFX S 0 s .\n").unwrap();
+        // `café` in latin-1
+        std::fs::write(&dic, b"3\ncaf\xe9/S\nwork/S\n").unwrap();
+
+        let dict = Hunspell::load(&dic, &aff).unwrap();
+        assert_eq!(dict.expand(), vec!["café", "cafés", "work", "works"]);
+
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_hunspell_words(&dict)
+            .unwrap()
+            .build();
+        assert!(checker.check("works"));
+        assert!(checker.check("cafés"));
+        assert!(!checker.check("cafes"));
+        assert_eq!(checker.suggest("cafes", 1)[0].word, "cafés");
+
+        // Words already added aren't replaced
+        let mut builder = SpellCheckerBuilder::<English>::new();
+        builder.add_ascii_words(vec!["dog".to_owned()]).unwrap();
+        assert!(builder.add_hunspell_words(&dict).is_none());
+        let checker = builder.build();
+        assert!(checker.check("dog"));
+        assert!(!checker.check("works"));
+
+        std::fs::remove_file(&aff).unwrap();
+        std::fs::remove_file(&dic).unwrap();
+    }
+}
@@ -0,0 +1,66 @@
+mod common;
+
+#[cfg(test)]
+mod identifiers_tests {
+    use spel_right::{English, SC, identifier_parts, tokenize_code};
+
+    use crate::common;
+
+    fn parts(identifier: &str) -> Vec<&str> {
+        identifier_parts(identifier)
+            .into_iter()
+            .map(|part| &identifier[part])
+            .collect()
+    }
+
+    #[test]
+    fn splits_identifiers() {
+        assert_eq!(
+            parts("parseHttpResponse"),
+            vec!["parse", "Http", "Response"]
+        );
+        assert_eq!(
+            parts("parseHTTPResponse2"),
+            vec!["parse", "HTTP", "Response"]
+        );
+        assert_eq!(parts("snake_case_name"), vec!["snake", "case", "name"]);
+        assert_eq!(parts("MAX_VALUE"), vec!["MAX", "VALUE"]);
+        assert_eq!(parts("__init__"), vec!["init"]);
+        assert_eq!(parts("utf8Decoder"), vec!["utf", "Decoder"]);
+        assert_eq!(parts("getX"), vec!["get", "X"]);
+        assert_eq!(parts("ÜberGröße"), vec!["Über", "Größe"]);
+        assert!(parts("42").is_empty());
+
+        let tokens = tokenize_code("let ä = getX(maxValue);");
+        let words: Vec<&str> = tokens.iter().map(|token| token.word).collect();
+        assert_eq!(words, vec!["let", "get", "max", "Value"]);
+        assert_eq!(tokens[3].span, 17..22);
+        assert_eq!(tokens[3].char_span, 16..21);
+    }
+
+    #[test]
+    fn checks_identifier_parts() {
+        let checker: SC<English> = common::checker(
+            &["parse", "http", "response", "max", "value", "let", "get"],
+            &[],
+            &[],
+        );
+        let code = "let response = parseHttpRespnse(MAX_VALU, getX);";
+        let misspellings = checker.suggest_code(code, 1);
+        let words: Vec<&str> = misspellings.iter().map(|m| m.word).collect();
+        assert_eq!(words, vec!["Respnse", "VALU"]);
+        assert_eq!(&code[misspellings[0].span.clone()], "Respnse");
+        assert_eq!(misspellings[0].suggestions[0].word, "Response");
+        assert_eq!(misspellings[1].suggestions[0].word, "VALUE");
+
+        // Replacing the span fixes the identifier
+        let mut fixed = code.to_owned();
+        for misspelling in misspellings.iter().rev() {
+            fixed.replace_range(misspelling.span.clone(), &misspelling.suggestions[0].word);
+        }
+        assert_eq!(fixed, "let response = parseHttpResponse(MAX_VALUE, getX);");
+
+        assert_eq!(checker.check_code(code).len(), 2);
+        assert!(checker.check_code("parse_http_response").is_empty());
+    }
+}
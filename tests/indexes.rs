@@ -0,0 +1,156 @@
+mod common;
+
+#[cfg(test)]
+mod index_tests {
+    use spel_right::{
+        DeletionIndex, English, MatchAlgorithm, SC, SpellChecker, SpellCheckerBuilder,
+        SuggestionIndex,
+    };
+
+    use crate::common::temp_path;
+
+    static WORDS_FILE: &str = "words.txt";
+
+    static MISSPELLED: [&str; 16] = [
+        "teh",
+        "quik",
+        "broown",
+        "foz",
+        "jumsp",
+        "oevr",
+        "adn",
+        "nothng",
+        "abuot",
+        "statemant",
+        "algorthm",
+        "struckture",
+        "keybord",
+        "hardwear",
+        "funciton",
+        "diferently",
+    ];
+
+    fn suggestions(checker: &SpellChecker, words: &[&str]) -> Vec<Vec<(String, usize)>> {
+        words
+            .iter()
+            .map(|word| {
+                checker
+                    .suggest(word, 0)
+                    .into_iter()
+                    .map(|s| (s.word.to_string(), s.distance))
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn deletion_index_matches_scan() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        checker.build_deletion_index();
+        let index = std::mem::take(&mut checker.index);
+
+        for algorithm in [
+            MatchAlgorithm::Greedy,
+            MatchAlgorithm::Osa,
+            MatchAlgorithm::DamerauLevenshtein,
+        ] {
+            checker.algorithm = algorithm;
+            // Index built for a bigger difference works for smaller ones too
+            for max_dif in [2, 1] {
+                checker.max_dif = max_dif;
+                checker.index = SuggestionIndex::Scan;
+                let scanned = suggestions(&checker, &MISSPELLED);
+                checker.index = index.clone();
+                assert_eq!(suggestions(&checker, &MISSPELLED), scanned);
+            }
+        }
+    }
+
+    #[test]
+    fn deletion_index_save_load() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        let index = DeletionIndex::new(&checker.word_groups, 1, DeletionIndex::DEFAULT_PREFIX_LEN);
+        let file = temp_path("deletion_index_test.bin");
+        index.save(&file).unwrap();
+
+        checker.max_dif = 1;
+        checker.load_deletion_index(&file).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        assert!(matches!(checker.index, SuggestionIndex::Deletion(_)));
+        let suggestions = checker.suggest("foz", 0);
+        assert!(suggestions.iter().any(|s| s.word == "fox"));
+    }
+
+    /// Words of up to 5 letters, building a tree over the whole dataset is too slow for a test.
+    fn short_words_checker() -> SpellChecker {
+        let content = std::fs::read_to_string(WORDS_FILE).unwrap();
+        let short: Vec<&str> = content.lines().take(10).collect();
+        let file = temp_path("short_words_test.txt");
+        std::fs::write(&file, short.join("\n")).unwrap();
+        let checker = SpellChecker::new(&file);
+        std::fs::remove_file(&file).unwrap();
+        checker
+    }
+
+    #[test]
+    fn bk_tree_matches_scan() {
+        let mut checker = short_words_checker();
+        checker.build_bk_tree();
+        let index = std::mem::take(&mut checker.index);
+
+        let words = [
+            "teh", "quik", "foz", "oevr", "adn", "doog", "jumsp", "tiis", "lazey",
+        ];
+        for algorithm in [
+            MatchAlgorithm::Greedy,
+            MatchAlgorithm::Osa,
+            MatchAlgorithm::DamerauLevenshtein,
+        ] {
+            checker.algorithm = algorithm;
+            for max_dif in [1, 2] {
+                checker.max_dif = max_dif;
+                checker.index = SuggestionIndex::Scan;
+                let scanned = suggestions(&checker, &words);
+                checker.index = index.clone();
+                assert_eq!(suggestions(&checker, &words), scanned);
+            }
+        }
+    }
+
+    #[test]
+    fn bk_tree_saved_with_dictionary() {
+        let words = [
+            "the", "quick", "brown", "fox", "jumps", "over", "lazy", "dog",
+        ];
+        let builder = || {
+            let mut builder = SpellCheckerBuilder::new();
+            builder
+                .add_ascii_words(words.iter().map(|w| w.to_string()).collect())
+                .unwrap();
+            builder
+        };
+        let checker: SC<English> = builder().with_bk_tree().build();
+
+        let json = serde_json::to_string(&checker).unwrap();
+        assert!(json.contains("BkTree"));
+        let loaded: SC<English> = serde_json::from_str(&json).unwrap();
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+
+        // The deserialized tree answers the same as scanning the words
+        let scanned: SC<English> = builder().build();
+        let suggestions = |checker: &SC<English>, word: &str| -> Vec<(String, usize)> {
+            checker
+                .suggest(word, 0)
+                .into_iter()
+                .map(|s| (s.word.to_string(), s.distance))
+                .collect()
+        };
+        for word in [
+            "teh", "quik", "foz", "jumsp", "oevr", "doog", "lazey", "brwn",
+        ] {
+            assert!(!suggestions(&loaded, word).is_empty());
+            assert_eq!(suggestions(&loaded, word), suggestions(&scanned, word));
+        }
+    }
+}
@@ -0,0 +1,89 @@
+#[cfg(test)]
+mod keyboard_tests {
+    use spel_right::{
+        English, ErrorModel, German, KeyboardLayout, Language, MatchAlgorithm, SC, SpellChecker,
+        SpellCheckerBuilder,
+        keyboard::{Azerty, Dvorak, Qwerty, Qwertz},
+    };
+
+    static WORDS_FILE: &str = "words.txt";
+
+    #[test]
+    fn adjacent_keys() {
+        assert!(Qwerty::are_adjacent('z', 'x'));
+        assert!(Qwerty::are_adjacent('a', 'q'));
+        assert!(Qwerty::are_adjacent('g', 'y'));
+        assert!(!Qwerty::are_adjacent('e', 'o'));
+        assert!(!Qwerty::are_adjacent('a', 'a'));
+        assert!(!Qwerty::are_adjacent('a', 'x'));
+
+        let mut neighbours = Qwerty::neighbours('s');
+        neighbours.sort();
+        assert_eq!(neighbours, vec!['a', 'd', 'e', 'w', 'x', 'z']);
+
+        assert!(Azerty::are_adjacent('a', 'z'));
+        assert!(Azerty::are_adjacent('m', 'ù'));
+        assert!(Qwertz::are_adjacent('z', 't'));
+        assert!(!Qwertz::are_adjacent('z', 'x'));
+        assert!(Dvorak::are_adjacent('o', 'e'));
+
+        for (a, b) in Dvorak::adjacent_pairs() {
+            assert!(Dvorak::adjacent_pairs().contains(&(b, a)));
+        }
+        assert_eq!(<English as Language>::Keyboard::name(), Qwerty::name());
+    }
+
+    #[test]
+    fn adjacent_substitutions_rank_first() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        checker.algorithm = MatchAlgorithm::Osa;
+        assert_ne!(checker.suggest("wprd", 1)[0].word, "word");
+        assert_ne!(checker.suggest("nighr", 1)[0].word, "night");
+
+        let position = |checker: &SpellChecker, word: &str| {
+            let suggestions = checker.suggest("foz", 0);
+            suggestions.iter().position(|s| s.word == word).unwrap()
+        };
+        assert!(position(&checker, "fox") > position(&checker, "fog"));
+
+        let mut model = ErrorModel::new();
+        model.set_keyboard::<Qwerty>(0.5);
+        checker.scorer = Box::new(model);
+        assert_eq!(checker.suggest("wprd", 1)[0].word, "word");
+        assert_eq!(checker.suggest("nighr", 1)[0].word, "night");
+        // `z` and `x` are next to each other, `z` and `g` are not
+        assert!(position(&checker, "fox") < position(&checker, "fog"));
+
+        checker.scorer = Box::new(ErrorModel::parse("keyboard dvorak 0.5").unwrap());
+        assert_eq!(checker.suggest("netwerk", 1)[0].word, "network");
+        assert!(ErrorModel::parse("keyboard colemak 0.5").is_err());
+    }
+
+    #[test]
+    fn language_keyboard_ranks_by_default() {
+        let words = || -> Vec<String> {
+            ["fog", "fox", "fo", "foxes"]
+                .iter()
+                .map(|w| w.to_string())
+                .collect()
+        };
+        let english: SC<English> = SpellCheckerBuilder::new()
+            .add_ascii_words(words())
+            .unwrap()
+            .build();
+        // `z` and `x` are next to each other on qwerty, so `fox` comes first of the words one edit away
+        let suggestions: Vec<String> = english
+            .suggest("foz", 0)
+            .iter()
+            .map(|s| s.word.to_string())
+            .collect();
+        assert_eq!(suggestions[..3], ["fox", "fo", "fog"]);
+
+        // `z` and `x` aren't next to each other on qwertz
+        let german: SC<German> = SpellCheckerBuilder::new()
+            .add_ascii_words(words())
+            .unwrap()
+            .build();
+        assert_ne!(german.suggest("foz", 1)[0].word, "fox");
+    }
+}
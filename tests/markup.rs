@@ -0,0 +1,73 @@
+mod common;
+
+#[cfg(test)]
+mod markup_tests {
+    use spel_right::{English, Markup, SC, SpellCheckerBuilder};
+
+    use crate::common::temp_path;
+
+    fn prose(markup: Markup, source: &str) -> String {
+        markup
+            .extract(source)
+            .into_iter()
+            .map(|range| &source[range])
+            .collect::<Vec<_>>()
+            .join("|")
+    }
+
+    #[test]
+    fn markdown_prose() {
+        let source = "# Title\n\nSee [the docs](https://docs.rs/x_y) and `let x`, <br/> www.example.com\n\n```rust\nfn main() {}\n```\n[docs]: https://example.com\n~~~\ncode\n~~~\nEnd ``a ` b`` [ref][label] ![alt](img.png)";
+        assert_eq!(
+            prose(Markup::Markdown, source),
+            "# Title\n\nSee [the docs| and |, | |\n\n|End | [ref| ![alt"
+        );
+    }
+
+    #[test]
+    fn html_prose() {
+        let source = "<!DOCTYPE html><p class=\"intro\">Hello &amp; <b>world</b></p><!-- note --><SCRIPT>let x;</script><pre>code</PRE>end";
+        assert_eq!(prose(Markup::Html, source), "Hello | |world|end");
+    }
+
+    #[test]
+    fn latex_prose() {
+        let source = "\\documentclass[12pt]{article}\n\\section{Intro} Text $x^2$ and \\(y\\), see \\ref{sec:one}. % a comment\n\\begin{equation}\nE = mc^2\n\\end{equation}\n\\textbf{Bold}\\\\ 50\\% done";
+        assert_eq!(
+            prose(Markup::Latex, source),
+            "\n|{Intro} Text | and |, see |. |\n|{Bold}| 50| done"
+        );
+    }
+
+    #[test]
+    fn checks_markup_files() {
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_ascii_words(
+                ["see", "the", "docs", "and", "text", "world"]
+                    .iter()
+                    .map(|w| w.to_string())
+                    .collect(),
+            )
+            .unwrap()
+            .build();
+        let file = temp_path("markup_test.md");
+        std::fs::write(
+            &file,
+            "See [teh docs](https://docs.rs/wrld)\n\n`wrld` and wrld",
+        )
+        .unwrap();
+        let diagnostics = checker.check_file(&file, 1).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let positions: Vec<(&str, usize, usize)> = diagnostics
+            .iter()
+            .map(|d| (d.word.as_str(), d.line, d.column))
+            .collect();
+        assert_eq!(positions, vec![("teh", 1, 6), ("wrld", 3, 12)]);
+        assert_eq!(diagnostics[1].suggestions[0].word, "world");
+
+        assert_eq!(Markup::from_path("index.HTM"), Some(Markup::Html));
+        assert_eq!(Markup::from_path("README.Md"), Some(Markup::Markdown));
+        assert_eq!(Markup::from_path("paper.tex"), Some(Markup::Latex));
+    }
+}
@@ -0,0 +1,74 @@
+#[cfg(test)]
+mod matching_tests {
+    use spel_right::{Edit, MatchAlgorithm, edit_script};
+
+    #[test]
+    fn transpositions_are_one_edit() {
+        for algorithm in [MatchAlgorithm::Osa, MatchAlgorithm::DamerauLevenshtein] {
+            assert_eq!(algorithm.distance(b"teh", b"the", 2), Some(1));
+            assert_eq!(algorithm.distance(b"funciton", b"function", 2), Some(1));
+            assert_eq!(algorithm.distance(b"abuot", b"about", 2), Some(1));
+        }
+    }
+
+    #[test]
+    fn exact_distances() {
+        for algorithm in [MatchAlgorithm::Osa, MatchAlgorithm::DamerauLevenshtein] {
+            assert_eq!(algorithm.distance(b"kitten", b"sitting", 3), Some(3));
+            assert_eq!(algorithm.distance(b"nothng", b"nothing", 2), Some(1));
+            assert_eq!(algorithm.distance(b"same", b"same", 0), Some(0));
+            assert_eq!(algorithm.distance(b"", b"abc", 3), Some(3));
+        }
+        // Restricted alignment can't edit a transposed pair again
+        assert_eq!(MatchAlgorithm::Osa.distance(b"ca", b"abc", 3), Some(3));
+        assert_eq!(
+            MatchAlgorithm::DamerauLevenshtein.distance(b"ca", b"abc", 3),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn respects_max_dif() {
+        for algorithm in [
+            MatchAlgorithm::Greedy,
+            MatchAlgorithm::Osa,
+            MatchAlgorithm::DamerauLevenshtein,
+        ] {
+            assert_eq!(algorithm.distance(b"kitten", b"sitting", 2), None);
+            assert_eq!(algorithm.distance(b"a", b"abcd", 2), None);
+        }
+        assert_eq!(
+            MatchAlgorithm::Greedy.distance(b"nothng", b"nothing", 2),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn edit_scripts() {
+        let script = edit_script(b"ca", b"abc", MatchAlgorithm::DamerauLevenshtein);
+        assert_eq!(
+            script,
+            vec![
+                Edit::Transpose { pos: 0, candidate_pos: 0 },
+                Edit::Insert { pos: 1, candidate_pos: 1 },
+            ]
+        );
+        assert_eq!(edit_script(b"ca", b"abc", MatchAlgorithm::Osa).len(), 3);
+        assert_eq!(
+            edit_script(b"kitten", b"sitting", MatchAlgorithm::Osa),
+            vec![
+                Edit::Substitute { pos: 0, candidate_pos: 0 },
+                Edit::Substitute { pos: 4, candidate_pos: 4 },
+                Edit::Insert { pos: 6, candidate_pos: 6 },
+            ]
+        );
+        assert!(edit_script(b"same", b"same", MatchAlgorithm::Osa).is_empty());
+    }
+
+    #[test]
+    fn works_on_chars() {
+        let word: Vec<char> = "прывет".chars().collect();
+        let candidate: Vec<char> = "привет".chars().collect();
+        assert_eq!(MatchAlgorithm::Osa.distance(&word, &candidate, 2), Some(1));
+    }
+}
@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod normalized_tests {
+    use spel_right::{
+        English, SC, SpellCheckerBuilder,
+        spell_checkers::{
+            SpellCheckerTrait,
+            normalized::{self, normalize, words_to_norm_groups},
+        },
+    };
+
+    fn words() -> Vec<String> {
+        [
+            "café",
+            "cafe",
+            "naïve",
+            "résumé",
+            "resume",
+            "straße",
+            "façade",
+            "über",
+            "fiancée",
+            "привет",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+    }
+
+    fn checker() -> normalized::SpellChecker {
+        normalized::SpellChecker::new(words_to_norm_groups(words()).unwrap())
+    }
+
+    fn suggestions(
+        checker: &normalized::SpellChecker,
+        word: &str,
+        max_dif: usize,
+    ) -> Vec<(String, usize)> {
+        checker
+            .suggest_for_word(word, max_dif)
+            .iter()
+            .map(|s| (s.word.to_string(), s.distance))
+            .collect()
+    }
+
+    #[test]
+    fn strips_diacritics() {
+        assert_eq!(normalize("café").as_deref(), Some("cafe"));
+        assert_eq!(normalize("naïve").as_deref(), Some("naive"));
+        assert_eq!(normalize("straße").as_deref(), Some("strasse"));
+        assert_eq!(normalize("façade").as_deref(), Some("facade"));
+        assert_eq!(normalize("привет"), None);
+
+        let groups = words_to_norm_groups(words()).unwrap();
+        // `cafe` and `café` share the ascii form
+        assert_eq!(groups[3].count(), 3);
+        assert_eq!(groups[3].utf8(0), Some("cafe"));
+        assert_eq!(groups[3].utf8(1), Some("café"));
+        assert_eq!(groups[3].find_ascii("cafe"), 0..2);
+    }
+
+    #[test]
+    fn checks_utf8_forms() {
+        let checker = checker();
+        assert!(checker.check("café"));
+        assert!(checker.check("Naïve"));
+        assert!(!checker.check("strasse"));
+        assert!(checker.check("straße"));
+        assert!(!checker.check("naive"));
+        assert!(!checker.check("привет"));
+
+        let mut forms = checker.forms("resume");
+        forms.sort();
+        assert_eq!(forms, vec!["resume", "résumé"]);
+        assert_eq!(checker.forms("Facade"), vec!["façade"]);
+
+        let id = checker.find("résumé").unwrap();
+        assert_eq!(id.len, 6);
+        assert_eq!(checker.get(id), Some("résumé"));
+        assert_eq!(checker.get_unchecked(id), "résumé");
+    }
+
+    #[test]
+    fn suggests_accented_words() {
+        let scan = checker();
+        assert_eq!(
+            suggestions(&scan, "naive", 0),
+            vec![("naïve".to_owned(), 1)]
+        );
+        assert_eq!(
+            suggestions(&scan, "cafe", 0),
+            vec![("cafe".to_owned(), 0), ("café".to_owned(), 1)]
+        );
+        assert_eq!(
+            suggestions(&scan, "strasse", 0),
+            vec![("straße".to_owned(), 2)]
+        );
+        // Typos are counted on the ascii forms
+        assert_eq!(suggestions(&scan, "fiancee", 1)[0].0, "fiancée");
+        assert_eq!(
+            suggestions(&scan, "facdae", 1),
+            vec![("façade".to_owned(), 2)]
+        );
+
+        let mut tree = checker();
+        tree.build_bk_tree();
+        for word in ["naive", "cafe", "resme", "fiance", "uber", "x"] {
+            for max_dif in 0..=2 {
+                let mut found = suggestions(&tree, word, max_dif);
+                let mut expected = suggestions(&scan, word, max_dif);
+                found.sort();
+                expected.sort();
+                assert_eq!(found, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn completes_without_diacritics() {
+        let checker = checker();
+        assert_eq!(checker.complete("caf", 0), vec!["cafe", "café"]);
+        assert_eq!(checker.complete("re", 1), vec!["resume"]);
+        assert_eq!(checker.complete("ré", 0), vec!["résumé"]);
+    }
+
+    #[test]
+    fn saved_with_dictionary() {
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_norm_words(words())
+            .unwrap()
+            .with_bk_tree()
+            .build();
+        let json = serde_json::to_string(&checker).unwrap();
+        let loaded: SC<English> = serde_json::from_str(&json).unwrap();
+        assert!(loaded.check("über"));
+        assert!(!loaded.check("ubr"));
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+
+        // "привет" has no ascii form, so it isn't counted
+        let json = serde_json::to_value(&checker).unwrap();
+        assert_eq!(json["dict_meta"]["words_amount"], words().len() - 1);
+    }
+}
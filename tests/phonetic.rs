@@ -0,0 +1,98 @@
+#[cfg(test)]
+mod phonetic_tests {
+    use spel_right::{
+        English, ErrorModel, FrequencyScorer, PhoneticAlgorithm, Scorer, SpellChecker,
+        phonetic::{Metaphone, Soundex},
+    };
+
+    static WORDS_FILE: &str = "words.txt";
+
+    #[test]
+    fn soundex_keys() {
+        for (word, key) in [
+            ("Robert", "R163"),
+            ("Rupert", "R163"),
+            ("Ashcraft", "A261"),
+            ("Tymczak", "T522"),
+            ("Pfister", "P236"),
+            ("Honeyman", "H555"),
+            ("Lee", "L000"),
+            ("", ""),
+        ] {
+            assert_eq!(Soundex.key(word), key, "{word}");
+        }
+    }
+
+    #[test]
+    fn metaphone_keys() {
+        for (word, key) in [
+            ("knowledge", "NLJ"),
+            ("nolij", "NLJ"),
+            ("phonetic", "FNTK"),
+            ("fonetik", "FNTK"),
+            ("thumb", "0M"),
+            ("school", "SKL"),
+            ("which", "WX"),
+            ("xylophone", "SLFN"),
+            ("accent", "AKSNT"),
+            ("gnome", "NM"),
+            ("", ""),
+        ] {
+            assert_eq!(Metaphone.key(word), key, "{word}");
+        }
+    }
+
+    #[test]
+    fn suggest_sounds_alike() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        let words = |checker: &SpellChecker, word: &str| -> Vec<String> {
+            let suggestions = checker.suggest(word, 0);
+            suggestions.iter().map(|s| s.word.to_string()).collect()
+        };
+        assert!(!words(&checker, "fonetik").contains(&"phonetic".to_owned()));
+        assert!(!words(&checker, "nolij").contains(&"knowledge".to_owned()));
+
+        checker.build_phonetic_index::<English>();
+        assert!(words(&checker, "fonetik").contains(&"phonetic".to_owned()));
+        assert!(words(&checker, "nolij").contains(&"knowledge".to_owned()));
+
+        // Phonetic matches are ranked by the scorer like the others, plus the penalty
+        let is_sorted = |checker: &SpellChecker, scorer: &dyn Scorer| {
+            let scores: Vec<f64> = checker
+                .suggest("nolij", 0)
+                .iter()
+                .map(|s| {
+                    let penalty = if s.distance > checker.max_dif {
+                        checker.phonetic_penalty
+                    } else {
+                        0.
+                    };
+                    scorer.score(b"nolij", s) + penalty
+                })
+                .collect();
+            scores.windows(2).all(|pair| pair[0] <= pair[1])
+        };
+        assert!(is_sorted(&checker, &FrequencyScorer));
+        let suggestions = checker.suggest("nolij", 0);
+        assert!(suggestions.last().unwrap().distance > checker.max_dif);
+
+        // Moved ahead of the words two edits away
+        checker.phonetic_penalty = -3.;
+        assert!(is_sorted(&checker, &FrequencyScorer));
+        assert!(checker.suggest("nolij", 1)[0].distance > checker.max_dif);
+
+        checker.scorer = Box::new(ErrorModel::for_language::<English>());
+        checker.phonetic_penalty = 0.;
+        assert!(is_sorted(&checker, &ErrorModel::for_language::<English>()));
+        checker.phonetic_penalty = -5.;
+        assert!(is_sorted(&checker, &ErrorModel::for_language::<English>()));
+
+        let knowledge = checker
+            .suggest("nolij", 0)
+            .into_iter()
+            .find(|s| s.word == "knowledge")
+            .unwrap();
+        assert_eq!(knowledge.distance, knowledge.edits.len());
+        assert_eq!(checker.get(knowledge.id), Some("knowledge"));
+    }
+}
@@ -0,0 +1,76 @@
+mod common;
+
+#[cfg(test)]
+mod routing_tests {
+    use spel_right::{English, SC, SpellCheckerBuilder};
+
+    use crate::common::{self, to_strings};
+
+    fn checker() -> SC<English> {
+        common::checker(
+            &["hello", "world", "cafe"],
+            &["café", "naïve", "straße"],
+            &["привет", "мир", "καλημέρα", "tokyo"],
+        )
+    }
+
+    #[test]
+    fn routes_by_encoding() {
+        let checker = checker();
+        assert!(checker.check("hello"));
+        assert!(checker.check("Cafe"));
+        assert!(checker.check("café"));
+        assert!(checker.check("NAÏVE"));
+        assert!(checker.check("straße"));
+        assert!(checker.check("Привет"));
+        assert!(checker.check("καλημέρα"));
+
+        assert!(!checker.check("helo"));
+        assert!(!checker.check("naive"));
+        assert!(!checker.check("cafè"));
+        assert!(!checker.check("превет"));
+        assert!(!checker.check(""));
+    }
+
+    #[test]
+    fn ascii_words_fall_through() {
+        let checker = checker();
+        // Only the utf-8 checker has it
+        assert!(checker.check("tokyo"));
+
+        let trie: SC<English> = SpellCheckerBuilder::new()
+            .add_trie_words(to_strings(&["hello"]))
+            .unwrap()
+            .add_utf8_words(to_strings(&["мир"]))
+            .unwrap()
+            .build();
+        assert!(trie.check("hello"));
+        assert!(trie.check("мир"));
+        assert!(!trie.check("hell"));
+    }
+
+    #[test]
+    fn ascii_words_grouped_once() {
+        let checker: SC<English> = common::checker(&["", "cat", "Cat", "cart", "horse"], &[], &[]);
+        // The longest words have a group too
+        assert!(checker.check("horse"));
+        assert!(checker.check("cat"));
+        assert!(!checker.check(""));
+        assert_eq!(checker.complete("ca", 0), vec!["cart", "cat"]);
+    }
+
+    #[test]
+    fn batch_check_mixed_scripts() {
+        let checker = checker();
+        let words = ["world", "мир", "naïve", "wrld", "мирр"];
+        let expected = vec![
+            ("world", true),
+            ("мир", true),
+            ("naïve", true),
+            ("wrld", false),
+            ("мирр", false),
+        ];
+        assert_eq!(checker.batch_check(&words), expected);
+        assert_eq!(checker.batch_par_check(&words), expected);
+    }
+}
@@ -0,0 +1,93 @@
+mod common;
+
+#[cfg(test)]
+mod sc_suggest_tests {
+    use spel_right::{English, MatchAlgorithm, SC, SpellChecker};
+
+    use crate::common::{self, WORDS_FILE, dataset_words};
+
+    fn checker() -> SC<English> {
+        common::builder(
+            &[],
+            &["café", "naïve", "résumé"],
+            &["привет", "мир", "καλημέρα"],
+        )
+        .add_ascii_words(dataset_words())
+        .unwrap()
+        .build()
+    }
+
+    #[test]
+    fn suggest_matches_old_checker() {
+        let checker = checker();
+        let mut old = SpellChecker::new(WORDS_FILE);
+        old.algorithm = MatchAlgorithm::Osa;
+
+        for word in ["teh", "funciton", "nothng", "hardwear", "sistem"] {
+            let mut expected: Vec<(String, usize)> = old
+                .suggest(word, 0)
+                .iter()
+                .map(|s| (s.word.to_string(), s.distance))
+                .collect();
+            let mut found: Vec<(String, usize)> = checker
+                .suggest(word, 0)
+                .iter()
+                .map(|s| (s.word.to_string(), s.distance))
+                .collect();
+            assert_eq!(found[0].1, expected[0].1);
+            expected.sort();
+            found.sort();
+            assert_eq!(found, expected);
+        }
+
+        assert_eq!(checker.suggest("funciton", 1)[0].word, "function");
+        let exact = checker.suggest("Hello", 0);
+        assert_eq!(exact.len(), 1);
+        assert_eq!(exact[0].word, "hello");
+        assert_eq!(exact[0].distance, 0);
+    }
+
+    #[test]
+    fn suggest_from_matching_encoding() {
+        let checker = checker();
+        assert!(
+            checker
+                .suggest("naiv", 0)
+                .iter()
+                .any(|s| s.word == "naïve" && s.distance == 2)
+        );
+        assert!(
+            checker
+                .suggest("resme", 0)
+                .iter()
+                .any(|s| s.word == "résumé")
+        );
+
+        let privet = checker.suggest("превет", 0);
+        assert_eq!(privet[0].word, "привет");
+        assert!(privet.iter().all(|s| !s.word.is_ascii()));
+        assert_eq!(checker.suggest("καλημερα", 1)[0].word, "καλημέρα");
+        assert_eq!(checker.suggest("мир", 0).len(), 1);
+    }
+
+    #[test]
+    fn batch_suggest_matches_suggest() {
+        let mut checker = checker();
+        checker.max_dif = 1;
+        let words = ["teh", "quik", "naive", "превет", "hello"];
+
+        let batch = checker.batch_suggest(&words, 5);
+        let par = checker.batch_par_suggest(&words, 5);
+        assert_eq!(batch, par);
+        for (word, suggestions) in &batch {
+            assert_eq!(*suggestions, checker.suggest(word, 5));
+            assert!(suggestions.len() <= 5);
+            assert!(suggestions.iter().all(|s| s.distance <= 2));
+        }
+
+        let mut count = 0;
+        checker.batch_suggest_with(&words, 5, |_, _| count += 1);
+        assert_eq!(count, words.len());
+        assert_eq!(checker.batch_suggest_iter(&words, 5).count(), words.len());
+    }
+}
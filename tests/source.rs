@@ -0,0 +1,128 @@
+mod common;
+
+#[cfg(test)]
+mod source_tests {
+    use spel_right::{English, Language, SC, SourceLanguage, SpellChecker, TextChecker};
+
+    use crate::common::{self, temp_path};
+
+    fn extract(language: SourceLanguage, source: &str) -> Vec<&str> {
+        language
+            .extract(source)
+            .into_iter()
+            .map(|range| &source[range])
+            .collect()
+    }
+
+    #[test]
+    fn extracts_comments_and_strings() {
+        let rust = r##"
+/// Doc /* nested */ comment
+fn main() { /* outer /* inner */ still */
+    let c = '"'; let r = r#"raw "quoted""#; // tail
+    let s = "line\nnext"; fn f<'a>(x: &'a str) {}
+}"##;
+        assert_eq!(
+            extract(SourceLanguage::Rust, rust),
+            vec![
+                "/ Doc /* nested */ comment",
+                " outer /* inner */ still ",
+                "raw \"quoted\"",
+                " tail",
+                "line",
+                "next",
+            ]
+        );
+
+        let python = "# hash\nx = '''doc\n\"string\"''' + 'it\\'s' + \"#no\"";
+        assert_eq!(
+            extract(SourceLanguage::Python, python),
+            vec![" hash", "doc\n\"string\"", "it", "s", "#no"]
+        );
+
+        let js = "const t = `tmpl ${x}` + 'single' // line\n/* block */";
+        assert_eq!(
+            extract(SourceLanguage::JavaScript, js),
+            vec!["tmpl ${x}", "single", " line", " block "]
+        );
+
+        let go = "s := `raw\\n` + \"esc\" // c\nr := '\"'";
+        assert_eq!(extract(SourceLanguage::Go, go), vec!["raw\\n", "esc", " c"]);
+
+        // Unterminated strings stop at the end of the line
+        let c = "char *s = \"open\nint x; /* end";
+        assert_eq!(extract(SourceLanguage::C, c), vec!["open", " end"]);
+
+        assert_eq!(
+            SourceLanguage::from_path("src/main.tsx"),
+            Some(SourceLanguage::JavaScript)
+        );
+        assert_eq!(
+            SourceLanguage::from_path("lib.hpp"),
+            Some(SourceLanguage::C)
+        );
+        assert_eq!(SourceLanguage::from_path("MAIN.C"), Some(SourceLanguage::C));
+        assert_eq!(SourceLanguage::from_path("README.md"), None);
+    }
+
+    #[test]
+    fn checks_source_files() {
+        let checker: SC<English> = common::checker(
+            &[
+                "the", "parse", "response", "returns", "hello", "world", "snake", "case",
+            ],
+            &[],
+            &[],
+        );
+        let source = "/// Returns teh response\nfn parse_respnse() {\n    // héllo snake_cse\n    println!(\"Hello wrld\\n\");\n}\n";
+        let file = temp_path("source_test.rs");
+        std::fs::write(&file, source).unwrap();
+        let diagnostics = checker.check_file(&file, 1).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let positions: Vec<(&str, usize, usize)> = diagnostics
+            .iter()
+            .map(|d| (d.word.as_str(), d.line, d.column))
+            .collect();
+        // Identifiers outside comments and strings aren't checked
+        assert_eq!(
+            positions,
+            vec![
+                ("teh", 1, 13),
+                ("héllo", 3, 8),
+                ("cse", 3, 20),
+                ("wrld", 4, 21)
+            ]
+        );
+        assert_eq!(diagnostics[0].suggestions[0].word, "the");
+        assert_eq!(diagnostics[3].suggestions[0].word, "world");
+        assert_eq!(
+            diagnostics[0].to_string(),
+            format!("{}:1:13: teh => the", file.display())
+        );
+
+        let ranges = SourceLanguage::Rust.extract(source);
+        let misspellings = checker.check_ranges(source, &ranges);
+        assert_eq!(&source[misspellings[2].span.clone()], "cse");
+        assert!(checker.check_file("Cargo.toml", 0).is_err());
+    }
+
+    #[test]
+    fn checks_source_files_with_any_dictionary() {
+        let checker = SpellChecker::new("words.txt");
+        let text_checker = TextChecker::new(&checker, English::tokenizer());
+        let source = "# Returns teh dog's response\nprint(\"Hello wrld\")\n";
+        let file = temp_path("text_checker_test.py");
+        std::fs::write(&file, source).unwrap();
+        let diagnostics = text_checker.check_file(&file, 0).unwrap();
+        std::fs::remove_file(&file).unwrap();
+
+        let positions: Vec<(&str, usize, usize)> = diagnostics
+            .iter()
+            .map(|d| (d.word.as_str(), d.line, d.column))
+            .collect();
+        assert_eq!(positions, vec![("teh", 1, 11), ("wrld", 2, 14)]);
+        assert!(diagnostics[1].suggestions.iter().any(|s| s.word == "world"));
+        assert!(text_checker.check_file("Cargo.toml", 0).is_err());
+    }
+}
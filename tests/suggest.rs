@@ -1,24 +1,59 @@
 #[cfg(test)]
 mod suggest_tests {
-    use spel_right::SpellChecker;
+    use spel_right::{Edit, MatchAlgorithm, SpellChecker};
 
     static WORDS_FILE: &str = "words.txt";
 
     #[test]
     fn suggest_correctness() {
         let checker = SpellChecker::new(WORDS_FILE);
+        let suggestions = checker.suggest("diferently", 0);
         assert_eq!(
-            checker.suggest("diferently", 0),
+            suggestions.iter().map(|s| &*s.word).collect::<Vec<_>>(),
             vec![
                 "differently",
-                "divergently",
-                "referently",
+                "afferently",
                 "efferently",
-                "afferently"
+                "referently",
+                "divergently"
             ]
         );
     }
 
+    #[test]
+    fn suggest_returns_distance() {
+        let mut checker = SpellChecker::new(WORDS_FILE);
+        checker.algorithm = MatchAlgorithm::Osa;
+        let suggestions = checker.suggest_for_word(b"teh");
+        let the = suggestions.iter().find(|s| s.word == "the").unwrap();
+        assert_eq!(the.distance, 1);
+        assert_eq!(the.edits, vec![Edit::Transpose { pos: 1, candidate_pos: 1 }]);
+        assert_eq!(checker.get(the.id), Some("the"));
+        assert!(suggestions.iter().all(|s| s.distance <= checker.max_dif));
+
+        let suggestions = checker.suggest("funciton", 1);
+        assert_eq!(suggestions[0].word, "function");
+    }
+
+    #[test]
+    fn suggest_edits() {
+        let checker = SpellChecker::new(WORDS_FILE);
+        let suggestions = checker.suggest("nothng", 0);
+        let nothing = suggestions.iter().find(|s| s.word == "nothing").unwrap();
+        assert_eq!(nothing.distance, 1);
+        assert_eq!(nothing.edits, vec![Edit::Insert { pos: 4, candidate_pos: 4 }]);
+
+        let suggestions = checker.suggest("statemant", 0);
+        let statement = suggestions.iter().find(|s| s.word == "statement").unwrap();
+        assert_eq!(statement.edits, vec![Edit::Substitute { pos: 6, candidate_pos: 6 }]);
+
+        let found = checker.suggest("hello", 0);
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].word, "hello");
+        assert_eq!(found[0].distance, 0);
+        assert!(found[0].edits.is_empty());
+    }
+
     #[test]
     fn batch_suggest_30_incorrect_words() {
         let checker = SpellChecker::new(WORDS_FILE);
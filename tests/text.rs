@@ -0,0 +1,98 @@
+mod common;
+
+#[cfg(test)]
+mod text_tests {
+    use spel_right::{English, SC, Token, TokenizerRules};
+
+    use crate::common;
+
+    fn checker() -> SC<English> {
+        common::checker(
+            &[
+                "the", "dog", "do", "well", "known", "is", "a", "word", "cafe", "plays", "can",
+                "won",
+            ],
+            &["café"],
+            &[],
+        )
+    }
+
+    #[test]
+    fn tokenizes_words() {
+        let rules = TokenizerRules::default();
+        let words: Vec<&str> = rules
+            .tokenize("Don't stop: well-known café, 42 mp3s... (dogs') e-mail--")
+            .iter()
+            .map(|token| token.word)
+            .collect();
+        assert_eq!(
+            words,
+            vec!["Don't", "stop", "well-known", "café", "dogs", "e-mail"]
+        );
+
+        let rules = TokenizerRules {
+            join_hyphenated: false,
+            skip_digits: false,
+            ..rules
+        };
+        let words: Vec<&str> = rules
+            .tokenize("well-known mp3 2024")
+            .iter()
+            .map(|token| token.word)
+            .collect();
+        assert_eq!(words, vec!["well", "known", "mp3"]);
+
+        assert_eq!(
+            TokenizerRules::default().tokenize("über-cool ok")[1],
+            Token {
+                word: "ok",
+                span: 11..13,
+                char_span: 10..12,
+            }
+        );
+    }
+
+    #[test]
+    fn finds_misspellings_with_offsets() {
+        let checker = checker();
+        let text = "The café dgo’s well-knwon, don't plays 3rd wrd!";
+        let misspellings = checker.check_text(text);
+        let words: Vec<&str> = misspellings.iter().map(|m| m.word).collect();
+        assert_eq!(words, vec!["dgo’s", "knwon", "wrd"]);
+        for misspelling in &misspellings {
+            assert_eq!(&text[misspelling.span.clone()], misspelling.word);
+            let chars: String = text
+                .chars()
+                .skip(misspelling.char_span.start)
+                .take(misspelling.char_span.len())
+                .collect();
+            assert_eq!(chars, misspelling.word);
+        }
+        assert_eq!(misspellings[0].span, 10..17);
+        assert_eq!(misspellings[0].char_span, 9..14);
+        assert!(misspellings.iter().all(|m| m.suggestions.is_empty()));
+
+        assert!(checker.check_text("").is_empty());
+        assert!(checker.check_text("The dog's well-known word.").is_empty());
+        // `n't` and `'t` both end them, only the `can` and `won` stems are words
+        assert!(
+            checker
+                .check_text("The dog can't, a dog won’t do.")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn suggests_for_text() {
+        let checker = checker();
+        let misspellings = checker.suggest_text("The dpg's wrd", 1);
+        assert_eq!(misspellings.len(), 2);
+        assert_eq!(misspellings[0].suggestions[0].word, "dog's");
+        assert_eq!(misspellings[1].word, "wrd");
+        assert_eq!(misspellings[1].suggestions[0].word, "word");
+        assert_eq!(misspellings[1].span, 10..13);
+
+        let misspellings = checker.suggest_text("cqn't", 1);
+        assert_eq!(misspellings[0].suggestions[0].word, "can't");
+    }
+}
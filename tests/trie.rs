@@ -0,0 +1,70 @@
+mod common;
+
+#[cfg(test)]
+mod trie_tests {
+    use spel_right::{
+        English, MatchAlgorithm, SC, SpellCheckerBuilder,
+        spell_checkers::{SpellCheckerTrait, trie, words_to_groups},
+    };
+
+    use crate::common::dataset_words;
+
+    #[test]
+    fn trie_check() {
+        let checker = trie::SpellChecker::new(words_to_groups(dataset_words()).unwrap());
+        assert!(checker.check("hello"));
+        assert!(checker.check("Function"));
+        assert!(!checker.check("funciton"));
+        assert!(!checker.check("hellox"));
+
+        let id = checker.find("hello").unwrap();
+        assert_eq!(checker.get(id), Some("hello"));
+    }
+
+    #[test]
+    fn trie_suggest_matches_all_words() {
+        let words = dataset_words();
+        let checker = trie::SpellChecker::new(words_to_groups(words.clone()).unwrap());
+
+        for word in [
+            "teh",
+            "quik",
+            "funciton",
+            "nothng",
+            "hardwear",
+            "struckture",
+        ] {
+            let mut expected: Vec<(&str, usize)> = words
+                .iter()
+                .filter_map(|w| {
+                    let dist = MatchAlgorithm::Osa.distance(word.as_bytes(), w.as_bytes(), 2)?;
+                    Some((w.as_str(), dist))
+                })
+                .collect();
+            expected.sort();
+
+            let suggestions = checker.suggest_for_word(word.as_bytes(), 2);
+            let found: Vec<(&str, usize)> =
+                suggestions.iter().map(|s| (&*s.word, s.distance)).collect();
+            assert_eq!(found, expected);
+        }
+    }
+
+    #[test]
+    fn trie_from_builder() {
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_trie_words(vec!["Hello".into(), "world".into(), "help".into()])
+            .unwrap()
+            .build();
+        assert!(checker.check("hello"));
+        assert!(checker.check("help"));
+        assert!(!checker.check("hell"));
+
+        // The trie is a kind of checker, its words are ascii
+        let json = serde_json::to_value(&checker).unwrap();
+        assert_eq!(
+            json["dict_meta"]["included_encodings"],
+            serde_json::json!(["ascii"])
+        );
+    }
+}
@@ -0,0 +1,136 @@
+mod common;
+
+#[cfg(test)]
+mod user_dict_tests {
+    use std::path::PathBuf;
+
+    use spel_right::{DICT_VERSION, DictMetadata, SpellChecker};
+
+    use crate::common::temp_path;
+
+    fn dict_file(name: &str) -> PathBuf {
+        let file = temp_path(&format!("user_dict_{name}_test.txt"));
+        std::fs::write(&file, "3\ncatdog\n# 5 7\n").unwrap();
+        file
+    }
+
+    #[test]
+    fn sorted_insert_and_remove() {
+        let file = dict_file("sorted");
+        let mut checker = SpellChecker::new(&file);
+        checker.added_words_threshold = 0;
+
+        for word in ["cow", "ant", "Eel", "bat"] {
+            assert!(checker.add(word).unwrap());
+        }
+        assert!(!checker.add("cat").unwrap());
+        assert!(!checker.add("").unwrap());
+        assert_eq!(
+            checker.complete("", 0),
+            vec!["ant", "bat", "cat", "cow", "dog", "eel"]
+        );
+        let cat = checker.find("cat").unwrap();
+        assert_eq!(checker.word_groups[2].frequency(cat.offset), 5);
+        let cow = checker.find("cow").unwrap();
+        assert_eq!(checker.word_groups[2].frequency(cow.offset), 0);
+
+        // New groups are created for longer words
+        assert!(checker.add("horse").unwrap());
+        assert_eq!(checker.word_groups.len(), 5);
+        assert!(checker.check("horse"));
+
+        assert!(checker.remove("Dog").unwrap());
+        assert!(!checker.remove("dog").unwrap());
+        assert!(!checker.remove("giraffe").unwrap());
+        assert!(!checker.check("dog"));
+        assert_eq!(checker.complete("", 0).len(), 6);
+        assert_eq!(checker.unsaved_changes(), 6);
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn saves_back_to_file() {
+        let file = dict_file("save");
+        let mut checker = SpellChecker::new(&file);
+        checker.added_words_threshold = 2;
+
+        checker.add("ant").unwrap();
+        assert!(!SpellChecker::new(&file).check("ant"));
+        assert_eq!(checker.unsaved_changes(), 1);
+        // The second change reaches the threshold
+        checker.remove("dog").unwrap();
+        assert_eq!(checker.unsaved_changes(), 0);
+
+        checker.add("horse").unwrap();
+        checker.save().unwrap();
+        let loaded = SpellChecker::new(&file);
+        assert!(loaded.check("ant"));
+        assert!(loaded.check("horse"));
+        assert!(!loaded.check("dog"));
+        let cat = loaded.find("cat").unwrap();
+        assert_eq!(loaded.word_groups[2].frequency(cat.offset), 5);
+
+        // Words with whitespace would shift the other words of their group once reloaded
+        assert!(checker.add("b\nee").is_err());
+        assert!(checker.add("two words").is_err());
+        assert!(!checker.word_groups[3].insert("b\tee"));
+        checker.save().unwrap();
+        assert_eq!(
+            SpellChecker::new(&file).complete("", 0),
+            checker.complete("", 0)
+        );
+
+        let mut tmp = file.clone().into_os_string();
+        tmp.push(".tmp");
+        assert!(!PathBuf::from(tmp).exists());
+        std::fs::remove_file(&file).unwrap();
+    }
+
+    #[test]
+    fn failed_save_keeps_change() {
+        let dir = temp_path("user_dict_failed_save_test");
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("words.txt");
+        std::fs::write(&file, "3\ncatdog\n").unwrap();
+        let mut checker = SpellChecker::new(&file);
+        checker.added_words_threshold = 1;
+        std::fs::remove_dir_all(&dir).unwrap();
+
+        assert!(checker.add("ant").is_err());
+        assert!(checker.check("ant"));
+        assert_eq!(checker.unsaved_changes(), 1);
+        assert!(!checker.add("ant").unwrap());
+        assert!(checker.remove("cat").is_err());
+        assert!(!checker.check("cat"));
+        assert_eq!(checker.unsaved_changes(), 2);
+    }
+
+    #[test]
+    fn saves_back_to_binary_file() {
+        let file = temp_path("user_dict_binary_test.bin");
+        let meta = DictMetadata {
+            language_full: "English (US)".into(),
+            language_short: "en_us".into(),
+            version: DICT_VERSION,
+            words_amount: 2,
+            included_encodings: vec!["ascii".into()],
+        };
+        let text = dict_file("binary");
+        SpellChecker::new(&text).save_binary(&file, &meta).unwrap();
+        std::fs::remove_file(&text).unwrap();
+
+        let mut checker = SpellChecker::new_binary(&file).unwrap();
+        assert!(checker.word_groups.iter().any(|group| group.is_mapped()));
+        checker.add("bat").unwrap();
+        checker.save().unwrap();
+        // Words were copied out of the map before the file was replaced
+        assert!(checker.word_groups.iter().all(|group| !group.is_mapped()));
+        assert!(checker.check("cat"));
+
+        let loaded = SpellChecker::new_binary(&file).unwrap();
+        assert_eq!(loaded.complete("", 0), vec!["bat", "cat", "dog"]);
+        std::fs::remove_file(&file).unwrap();
+
+        assert!(SpellChecker::default().save().is_err());
+    }
+}
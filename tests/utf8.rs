@@ -0,0 +1,142 @@
+#[cfg(test)]
+mod utf8_tests {
+    use spel_right::{
+        Edit, English, SC, SpellCheckerBuilder,
+        spell_checkers::{
+            SpellCheckerTrait,
+            utf8::{self, words_to_char_groups},
+        },
+    };
+
+    fn words() -> Vec<String> {
+        [
+            "привет",
+            "привед",
+            "пирог",
+            "мир",
+            "мираж",
+            "καλημέρα",
+            "καλή",
+            "café",
+            "cafe",
+            "naïve",
+            "über",
+            "uber",
+            "straße",
+            "hello",
+        ]
+        .iter()
+        .map(|w| w.to_string())
+        .collect()
+    }
+
+    fn checker() -> utf8::SpellChecker {
+        utf8::SpellChecker::new(words_to_char_groups(words()).unwrap())
+    }
+
+    fn suggestions(
+        checker: &utf8::SpellChecker,
+        word: &str,
+        max_dif: usize,
+    ) -> Vec<(String, usize)> {
+        let mut found: Vec<(String, usize)> = checker
+            .suggest_for_word(word, max_dif)
+            .iter()
+            .map(|s| (s.word.to_string(), s.distance))
+            .collect();
+        found.sort();
+        found
+    }
+
+    #[test]
+    fn groups_by_chars() {
+        let groups = words_to_char_groups(words()).unwrap();
+        // `καλημέρα` has 8 chars and 16 bytes
+        assert_eq!(groups.len(), 8);
+        assert_eq!(groups[3].count(), 5);
+        assert_eq!(groups[3].get(0), Some("cafe"));
+        assert_eq!(groups[3].get(1), Some("café"));
+
+        let checker = checker();
+        assert!(checker.check("Привет"));
+        assert!(checker.check("ΚΑΛΗΜΈΡΑ"));
+        assert!(checker.check("café"));
+        assert!(!checker.check("caf"));
+        assert!(!checker.check("прив"));
+
+        let id = checker.find("straße").unwrap();
+        assert_eq!(id.len, 6);
+        assert_eq!(checker.get(id), Some("straße"));
+        assert_eq!(checker.get_unchecked(id), "straße");
+    }
+
+    #[test]
+    fn typo_in_multibyte_char_is_one_edit() {
+        let checker = checker();
+        let found = checker.suggest_for_word("превет", 1);
+        let privet = found.iter().find(|s| s.word == "привет").unwrap();
+        assert_eq!(privet.distance, 1);
+        assert_eq!(
+            privet.edits,
+            vec![Edit::Substitute {
+                pos: 2,
+                candidate_pos: 2
+            }]
+        );
+        assert_eq!(checker.get(privet.id), Some("привет"));
+
+        assert_eq!(
+            suggestions(&checker, "cafè", 1),
+            vec![("cafe".to_owned(), 1), ("café".to_owned(), 1)]
+        );
+        assert_eq!(
+            suggestions(&checker, "naive", 1),
+            vec![("naïve".to_owned(), 1)]
+        );
+        assert_eq!(
+            suggestions(&checker, "ubre", 1),
+            vec![("uber".to_owned(), 1)]
+        );
+        assert_eq!(
+            suggestions(&checker, "καλημερα", 1),
+            vec![("καλημέρα".to_owned(), 1)]
+        );
+    }
+
+    #[test]
+    fn bk_tree_matches_scan() {
+        let scan = checker();
+        let mut tree = checker();
+        tree.build_bk_tree();
+        for word in ["превет", "мирж", "καλι", "strasse", "ubr", "helo", "x"] {
+            for max_dif in 0..=2 {
+                assert_eq!(
+                    suggestions(&tree, word, max_dif),
+                    suggestions(&scan, word, max_dif)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn complete_chars() {
+        let checker = checker();
+        assert_eq!(checker.complete("ПРИ", 0), vec!["привед", "привет"]);
+        assert_eq!(checker.complete("ми", 0), vec!["мир", "мираж"]);
+        assert_eq!(checker.complete("caf", 1), vec!["cafe"]);
+    }
+
+    #[test]
+    fn saved_with_dictionary() {
+        let checker: SC<English> = SpellCheckerBuilder::new()
+            .add_utf8_words(words())
+            .unwrap()
+            .with_bk_tree()
+            .build();
+        let json = serde_json::to_string(&checker).unwrap();
+        let loaded: SC<English> = serde_json::from_str(&json).unwrap();
+        assert!(loaded.check("мираж"));
+        assert!(!loaded.check("мираш"));
+        assert_eq!(serde_json::to_string(&loaded).unwrap(), json);
+    }
+}